@@ -0,0 +1,513 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use tracing::info;
+
+use crate::{
+    puzzle,
+    submit::{self, SubmitResponse},
+    ClientError,
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_YEAR: u32 = 2024;
+pub const DEFAULT_MIN_DELAY: Duration =
+    Duration::from_secs(5);
+
+const USER_AGENT: &str =
+    "github.com/risangbaskoro/advent-of-code-rust-2024";
+/// Touched after every request so separate runs
+/// also respect the minimum delay.
+const LAST_REQUEST_FILE: &str = "last-request";
+
+/// A blocking Advent of Code client that caches
+/// everything it downloads and spaces out its
+/// requests.
+#[derive(Debug)]
+pub struct Client {
+    agent: ureq::Agent,
+    session: String,
+    year: u32,
+    base_url: String,
+    cache_dir: Option<PathBuf>,
+    min_delay: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .build(),
+            session: session.into(),
+            year: DEFAULT_YEAR,
+            base_url: BASE_URL.to_string(),
+            cache_dir: None,
+            min_delay: DEFAULT_MIN_DELAY,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Builds a client from the `SESSION`
+    /// environment variable.
+    pub fn from_env() -> Result<Self, ClientError> {
+        match std::env::var("SESSION") {
+            Ok(session) if !session.trim().is_empty() => {
+                Ok(Self::new(session.trim()))
+            }
+            _ => Err(ClientError::MissingSession),
+        }
+    }
+
+    pub fn with_year(mut self, year: u32) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// Points the client somewhere other than
+    /// adventofcode.com, e.g. a local mock
+    /// server.
+    pub fn with_base_url(
+        mut self,
+        base_url: impl Into<String>,
+    ) -> Self {
+        self.base_url = base_url
+            .into()
+            .trim_end_matches('/')
+            .to_string();
+        self
+    }
+
+    pub fn with_cache_dir(
+        mut self,
+        cache_dir: impl Into<PathBuf>,
+    ) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    pub fn with_min_delay(
+        mut self,
+        min_delay: Duration,
+    ) -> Self {
+        self.min_delay = min_delay;
+        self
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    fn cache_path(
+        &self,
+        day: u32,
+        file: &str,
+    ) -> Option<PathBuf> {
+        self.cache_dir.as_ref().map(|dir| {
+            dir.join(self.year.to_string())
+                .join(format!("day-{day:02}"))
+                .join(file)
+        })
+    }
+
+    fn read_cache(
+        &self,
+        day: u32,
+        file: &str,
+    ) -> Option<String> {
+        let path = self.cache_path(day, file)?;
+        let contents = fs::read_to_string(&path).ok()?;
+        info!("using cached `{}`", path.display());
+        Some(contents)
+    }
+
+    fn write_cache(
+        &self,
+        day: u32,
+        file: &str,
+        contents: &str,
+    ) -> Result<(), ClientError> {
+        if let Some(path) = self.cache_path(day, file) {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, contents)?;
+        }
+        Ok(())
+    }
+
+    /// Sleeps until at least `min_delay` has
+    /// passed since the previous request.
+    fn throttle(&self) -> Result<(), ClientError> {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let since_last = last_request
+            .map(|at| at.elapsed())
+            .into_iter()
+            .chain(self.since_last_request_file())
+            .min();
+        if let Some(wait) = since_last.and_then(|elapsed| {
+            self.min_delay.checked_sub(elapsed)
+        }) {
+            info!(
+                "waiting {wait:?} before the next request"
+            );
+            thread::sleep(wait);
+        }
+
+        *last_request = Some(Instant::now());
+        if let Some(dir) = &self.cache_dir {
+            fs::create_dir_all(dir)?;
+            fs::write(dir.join(LAST_REQUEST_FILE), "")?;
+        }
+        Ok(())
+    }
+
+    fn since_last_request_file(&self) -> Option<Duration> {
+        let dir = self.cache_dir.as_ref()?;
+        let modified =
+            fs::metadata(dir.join(LAST_REQUEST_FILE))
+                .and_then(|metadata| metadata.modified())
+                .ok()?;
+        SystemTime::now().duration_since(modified).ok()
+    }
+
+    fn get(
+        &self,
+        day: u32,
+        path: &str,
+    ) -> Result<String, ClientError> {
+        let url = format!("{}{path}", self.base_url);
+        self.send(day, self.agent.get(&url), |request| {
+            request.call()
+        })
+    }
+
+    fn post(
+        &self,
+        day: u32,
+        path: &str,
+        form: &[(&str, &str)],
+    ) -> Result<String, ClientError> {
+        let url = format!("{}{path}", self.base_url);
+        self.send(day, self.agent.post(&url), |request| {
+            request.send_form(form)
+        })
+    }
+
+    fn send<F>(
+        &self,
+        day: u32,
+        request: ureq::Request,
+        call: F,
+    ) -> Result<String, ClientError>
+    where
+        F: FnOnce(
+            ureq::Request,
+        )
+            -> Result<ureq::Response, ureq::Error>,
+    {
+        self.throttle()?;
+
+        let url = request.url().to_string();
+        info!("sending to `{url}`");
+        let response = call(request.set(
+            "Cookie",
+            &format!("session={}", self.session),
+        ));
+
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response
+                    .into_string()
+                    .unwrap_or_default();
+                Err(status_error(
+                    self.year, day, url, status, body,
+                ))
+            }
+            Err(ureq::Error::Transport(transport)) => Err(
+                ClientError::Transport(Box::new(transport)),
+            ),
+        }
+    }
+
+    /// The personal puzzle input for `day`.
+    pub fn input(
+        &self,
+        day: u32,
+    ) -> Result<String, ClientError> {
+        if let Some(input) =
+            self.read_cache(day, "input.txt")
+        {
+            return Ok(input);
+        }
+
+        let input = self.get(
+            day,
+            &format!("/{}/day/{day}/input", self.year),
+        )?;
+        if looks_like_html(&input) {
+            return Err(if is_logged_out(&input) {
+                ClientError::LoggedOut
+            } else {
+                ClientError::UnexpectedHtml
+            });
+        }
+
+        self.write_cache(day, "input.txt", &input)?;
+        Ok(input)
+    }
+
+    /// The raw HTML puzzle page for `day`. It is
+    /// only cached once both parts are unlocked.
+    pub fn puzzle_html(
+        &self,
+        day: u32,
+    ) -> Result<String, ClientError> {
+        if let Some(html) =
+            self.read_cache(day, "puzzle.html")
+        {
+            return Ok(html);
+        }
+
+        let html = self.get(
+            day,
+            &format!("/{}/day/{day}", self.year),
+        )?;
+        let articles = puzzle::articles(&html).len();
+        if articles == 0 {
+            return Err(ClientError::MissingArticle);
+        }
+
+        if articles >= 2 {
+            self.write_cache(day, "puzzle.html", &html)?;
+        }
+        Ok(html)
+    }
+
+    /// The puzzle description for `day` converted
+    /// to markdown.
+    pub fn puzzle_markdown(
+        &self,
+        day: u32,
+    ) -> Result<String, ClientError> {
+        puzzle::to_markdown(&self.puzzle_html(day)?)
+    }
+
+    /// Posts `answer` for `part` of `day`.
+    /// Nothing is cached, and the caller is
+    /// expected to check the history first.
+    pub fn submit(
+        &self,
+        day: u32,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitResponse, ClientError> {
+        let html = self.post(
+            day,
+            &format!("/{}/day/{day}/answer", self.year),
+            &[
+                ("level", &part.to_string()),
+                ("answer", answer),
+            ],
+        )?;
+        Ok(submit::parse_response(&html))
+    }
+}
+
+fn looks_like_html(body: &str) -> bool {
+    let start = body.trim_start().to_ascii_lowercase();
+    start.starts_with("<!doctype")
+        || start.starts_with("<html")
+}
+
+fn is_logged_out(body: &str) -> bool {
+    body.contains("Please log in")
+        || body.contains("[Log In]")
+}
+
+fn status_error(
+    year: u32,
+    day: u32,
+    url: String,
+    status: u16,
+    body: String,
+) -> ClientError {
+    if is_logged_out(&body) {
+        ClientError::LoggedOut
+    } else if status == 404
+        && body.contains("before it unlocks")
+    {
+        ClientError::NotUnlocked { year, day }
+    } else {
+        ClientError::Status { url, status, body }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::mock_server, submit::Verdict};
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "aoc-client-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_input_is_cached() -> miette::Result<()> {
+        let server = mock_server(vec![(200, "1 2\n3 4\n")]);
+        let dir = cache_dir("input");
+        let client = server.client().with_cache_dir(&dir);
+
+        assert_eq!("1 2\n3 4\n", client.input(1)?);
+        assert_eq!("1 2\n3 4\n", client.input(1)?);
+        assert_eq!(1, server.hits());
+        assert!(dir.join("2024/day-01/input.txt").exists());
+
+        server.finish();
+        fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_logged_out() {
+        let server = mock_server(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (
+                200,
+                "<!DOCTYPE html><html><a href=\"/2024/auth/login\">[Log In]</a></html>",
+            ),
+        ]);
+        let client = server.client();
+
+        assert!(matches!(
+            client.input(1),
+            Err(ClientError::LoggedOut)
+        ));
+        assert!(matches!(
+            client.input(1),
+            Err(ClientError::LoggedOut)
+        ));
+        server.finish();
+    }
+
+    #[test]
+    fn test_not_unlocked_and_other_errors() {
+        let server = mock_server(vec![
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.",
+            ),
+            (500, "Internal Server Error"),
+            (200, "<!DOCTYPE html><html>oops</html>"),
+        ]);
+        let client = server.client().with_year(2023);
+
+        assert!(matches!(
+            client.input(25),
+            Err(ClientError::NotUnlocked {
+                year: 2023,
+                day: 25
+            })
+        ));
+        assert!(matches!(
+            client.input(1),
+            Err(ClientError::Status { status: 500, .. })
+        ));
+        assert!(matches!(
+            client.input(1),
+            Err(ClientError::UnexpectedHtml)
+        ));
+        server.finish();
+    }
+
+    #[test]
+    fn test_min_delay() -> miette::Result<()> {
+        let server =
+            mock_server(vec![(200, "1"), (200, "2")]);
+        let client = server
+            .client()
+            .with_min_delay(Duration::from_millis(200));
+
+        let start = Instant::now();
+        client.input(1)?;
+        client.input(2)?;
+        assert!(
+            start.elapsed() >= Duration::from_millis(200)
+        );
+        server.finish();
+        Ok(())
+    }
+
+    #[test]
+    fn test_submit() -> miette::Result<()> {
+        let server = mock_server(vec![(
+            200,
+            "<html><main><article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article></main></html>",
+        )]);
+        let response =
+            server.client().submit(7, 2, "123")?;
+        assert_eq!(Verdict::TooLow, response.verdict);
+        assert_eq!(
+            Some(Duration::from_secs(60)),
+            response.wait
+        );
+
+        let requests = server.finish();
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("/2024/day/7/answer", requests[0].url);
+        assert_eq!("level=2&answer=123", requests[0].body);
+        Ok(())
+    }
+
+    #[test]
+    fn test_puzzle_markdown() -> miette::Result<()> {
+        let server = mock_server(vec![
+            (
+                200,
+                "<html><article class=\"day-desc\"><h2>--- Day 1: Test ---</h2></article></html>",
+            ),
+            (
+                200,
+                "<html><article class=\"day-desc\"><h2>--- Day 1: Test ---</h2></article><article class=\"day-desc\"><h2>--- Part Two ---</h2></article></html>",
+            ),
+        ]);
+        let dir = cache_dir("puzzle");
+        let client = server.client().with_cache_dir(&dir);
+
+        // only part one is unlocked, so it is not
+        // cached and the next call fetches again
+        let markdown = client.puzzle_markdown(1)?;
+        assert!(markdown.contains("Day 1: Test"));
+        assert!(!markdown.contains("Part Two"));
+
+        let markdown = client.puzzle_markdown(1)?;
+        assert!(markdown.contains("Part Two"));
+        assert!(client
+            .puzzle_markdown(1)?
+            .contains("Part Two"));
+        assert_eq!(2, server.hits());
+
+        server.finish();
+        fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ClientError {
+    #[error("no session token set")]
+    #[diagnostic(
+        code(aoc_client::missing_session),
+        help("set SESSION in your .env file, see the justfile for how to find it")
+    )]
+    MissingSession,
+    #[error("the session token was rejected")]
+    #[diagnostic(
+        code(aoc_client::logged_out),
+        help("the session cookie has probably expired, grab a fresh one")
+    )]
+    LoggedOut,
+    #[error("day {day} of {year} has not unlocked yet")]
+    #[diagnostic(code(aoc_client::not_unlocked))]
+    NotUnlocked { year: u32, day: u32 },
+    #[error("unexpected {status} response from `{url}`")]
+    #[diagnostic(code(aoc_client::status))]
+    Status {
+        url: String,
+        status: u16,
+        body: String,
+    },
+    #[error("expected puzzle input but got an HTML page")]
+    #[diagnostic(
+        code(aoc_client::unexpected_html),
+        help("this is usually an error or login page")
+    )]
+    UnexpectedHtml,
+    #[error("puzzle page has no description")]
+    #[diagnostic(code(aoc_client::missing_article))]
+    MissingArticle,
+    #[error("could not read or write `{}`", path.display())]
+    #[diagnostic(code(aoc_client::history))]
+    History {
+        path: std::path::PathBuf,
+        #[help]
+        reason: String,
+    },
+    #[error("request failed")]
+    #[diagnostic(code(aoc_client::transport))]
+    Transport(#[from] Box<ureq::Transport>),
+    #[error(transparent)]
+    #[diagnostic(code(aoc_client::io))]
+    Io(#[from] std::io::Error),
+}
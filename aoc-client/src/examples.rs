@@ -0,0 +1,154 @@
+use crate::{
+    puzzle::{articles, elements},
+    ClientError,
+};
+
+/// An example input and its expected answer, as
+/// given in the puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<String>,
+}
+
+/// Pulls the example input and answer for each
+/// unlocked part out of a puzzle page.
+///
+/// The example input is the first of the longest
+/// `<pre><code>` blocks in the part's
+/// description, since smaller blocks tend to be
+/// illustrations. The answer is the last
+/// emphasized `<code>`. Part two usually reuses
+/// part one's example, so it falls back to that
+/// input when it has none of its own.
+pub fn extract(
+    html: &str,
+) -> Result<Vec<Example>, ClientError> {
+    let articles = articles(html);
+    if articles.is_empty() {
+        return Err(ClientError::MissingArticle);
+    }
+
+    let mut examples: Vec<Example> = vec![];
+    for (index, article) in articles.iter().enumerate() {
+        let input = elements(
+            article,
+            "<pre><code>",
+            "</code></pre>",
+        )
+        .into_iter()
+        .map(text)
+        // `max_by_key` keeps the last maximum
+        .rev()
+        .max_by_key(String::len)
+        .or_else(|| {
+            examples
+                .first()
+                .map(|example| example.input.clone())
+        });
+
+        let answer = answer(article);
+
+        if let Some(input) = input {
+            examples.push(Example {
+                part: index as u8 + 1,
+                input,
+                answer,
+            });
+        }
+    }
+
+    Ok(examples)
+}
+
+/// The last emphasized `<code>` in `article`,
+/// written either way round.
+fn answer(article: &str) -> Option<String> {
+    let start = ["<code><em>", "<em><code>"]
+        .iter()
+        .filter_map(|open| article.rfind(open))
+        .max()?;
+    let rest = &article[start..];
+    let end = ["</em></code>", "</code></em>"]
+        .iter()
+        .filter_map(|close| {
+            rest.find(close).map(|i| i + close.len())
+        })
+        .min()?;
+    Some(text(&rest[..end]))
+}
+
+/// Strips tags and decodes the entities AoC uses.
+pub(crate) fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 7: Bridge Repair ---</h2>
+<p>For example:</p>
+<pre><code>190: 10 19
+3267: 81 40 27
+83: 17 5
+</code></pre>
+<p>Only one way: <code>3267 = 81 + 40 * 27</code>.</p>
+<pre><code>10 &lt; 19</code></pre>
+<p>The total calibration result is <code><em>3749</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>123</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p><code>156: 15 6</code> can be made true with <code>15 || 6</code>.</p>
+<p>Adding up all six test values produces the new total calibration result of <em><code>11387</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract() -> miette::Result<()> {
+        let examples = extract(PAGE)?;
+        assert_eq!(
+            vec![
+                Example {
+                    part: 1,
+                    input: "190: 10 19\n3267: 81 40 27\n83: 17 5\n"
+                        .to_string(),
+                    answer: Some("3749".to_string()),
+                },
+                Example {
+                    part: 2,
+                    input: "190: 10 19\n3267: 81 40 27\n83: 17 5\n"
+                        .to_string(),
+                    answer: Some("11387".to_string()),
+                },
+            ],
+            examples
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            "a < b && <em>",
+            text("<code>a &lt; <em>b</em> &amp;&amp; &lt;em&gt;</code>")
+        );
+    }
+}
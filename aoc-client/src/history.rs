@@ -0,0 +1,371 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{submit::Verdict, ClientError};
+
+/// One answer we posted and what the site said
+/// about it.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct Submission {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Unix seconds when the answer was posted.
+    pub at: u64,
+    /// Seconds the site asked us to wait before
+    /// the next answer.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub wait: Option<u64>,
+}
+
+/// Why an answer should not be posted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved {
+        answer: String,
+    },
+    KnownWrong {
+        verdict: Verdict,
+    },
+    /// A previous answer that was too high is at
+    /// or below this one.
+    AboveTooHigh {
+        bound: String,
+    },
+    /// A previous answer that was too low is at
+    /// or above this one.
+    BelowTooLow {
+        bound: String,
+    },
+    Wait {
+        remaining: Duration,
+    },
+}
+
+impl std::fmt::Display for Refusal {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "already solved with `{answer}`")
+            }
+            Refusal::KnownWrong { verdict } => write!(
+                f,
+                "this answer was already rejected ({verdict:?})"
+            ),
+            Refusal::AboveTooHigh { bound } => {
+                write!(f, "`{bound}` was already too high")
+            }
+            Refusal::BelowTooLow { bound } => {
+                write!(f, "`{bound}` was already too low")
+            }
+            Refusal::Wait { remaining } => write!(
+                f,
+                "the site asked us to wait another {}s",
+                remaining.as_secs()
+            ),
+        }
+    }
+}
+
+/// Every answer posted so far, stored as TOML.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "submission")]
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self, ClientError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|e| ClientError::History {
+                    path: path.to_path_buf(),
+                    reason: e.to_string(),
+                }),
+            Err(e)
+                if e.kind()
+                    == std::io::ErrorKind::NotFound =>
+            {
+                Ok(Self::default())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(
+        &self,
+        path: &Path,
+    ) -> Result<(), ClientError> {
+        let contents = toml::to_string_pretty(self)
+            .map_err(|e| ClientError::History {
+                path: path.to_path_buf(),
+                reason: e.to_string(),
+            })?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(fs::write(path, contents)?)
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    pub fn record(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    /// Checks `answer` against everything we
+    /// already know about this part at time
+    /// `now`.
+    pub fn check(
+        &self,
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: &str,
+        now: SystemTime,
+    ) -> Result<(), Refusal> {
+        let now = unix_seconds(now);
+
+        if let Some(wait) = self
+            .submissions
+            .iter()
+            .filter(|s| s.year == year)
+            .filter_map(|s| s.wait.map(|wait| s.at + wait))
+            .max()
+            .and_then(|until| until.checked_sub(now))
+            .filter(|remaining| *remaining > 0)
+        {
+            return Err(Refusal::Wait {
+                remaining: Duration::from_secs(wait),
+            });
+        }
+
+        let previous =
+            self.submissions.iter().filter(|s| {
+                s.year == year
+                    && s.day == day
+                    && s.part == part
+            });
+        let value = answer.parse::<i128>().ok();
+
+        for submission in previous {
+            let bound =
+                submission.answer.parse::<i128>().ok();
+            match submission.verdict {
+                Verdict::Correct => {
+                    return Err(Refusal::AlreadySolved {
+                        answer: submission.answer.clone(),
+                    })
+                }
+                verdict
+                    if verdict.is_wrong()
+                        && submission.answer == answer =>
+                {
+                    return Err(Refusal::KnownWrong {
+                        verdict,
+                    })
+                }
+                Verdict::TooHigh
+                    if value.zip(bound).is_some_and(
+                        |(value, bound)| value >= bound,
+                    ) =>
+                {
+                    return Err(Refusal::AboveTooHigh {
+                        bound: submission.answer.clone(),
+                    })
+                }
+                Verdict::TooLow
+                    if value.zip(bound).is_some_and(
+                        |(value, bound)| value <= bound,
+                    ) =>
+                {
+                    return Err(Refusal::BelowTooLow {
+                        bound: submission.answer.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(
+        answer: &str,
+        verdict: Verdict,
+        at: u64,
+        wait: Option<u64>,
+    ) -> Submission {
+        Submission {
+            year: 2024,
+            day: 7,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+            at,
+            wait,
+        }
+    }
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn test_known_wrong_and_bounds() {
+        let mut history = History::default();
+        history.record(submission(
+            "100",
+            Verdict::TooHigh,
+            0,
+            Some(60),
+        ));
+        history.record(submission(
+            "10",
+            Verdict::TooLow,
+            100,
+            None,
+        ));
+        history.record(submission(
+            "abc",
+            Verdict::Incorrect,
+            200,
+            None,
+        ));
+
+        let check = |answer| {
+            history.check(2024, 7, 1, answer, at(1000))
+        };
+        assert_eq!(
+            Err(Refusal::KnownWrong {
+                verdict: Verdict::TooHigh
+            }),
+            check("100")
+        );
+        assert_eq!(
+            Err(Refusal::AboveTooHigh {
+                bound: "100".to_string()
+            }),
+            check("250")
+        );
+        assert_eq!(
+            Err(Refusal::BelowTooLow {
+                bound: "10".to_string()
+            }),
+            check("3")
+        );
+        assert_eq!(
+            Err(Refusal::KnownWrong {
+                verdict: Verdict::Incorrect
+            }),
+            check("abc")
+        );
+        assert_eq!(Ok(()), check("50"));
+        assert_eq!(
+            Ok(()),
+            history.check(2024, 7, 2, "100", at(1000))
+        );
+        assert_eq!(
+            Ok(()),
+            history.check(2023, 7, 1, "100", at(1000))
+        );
+    }
+
+    #[test]
+    fn test_wait_and_solved() {
+        let mut history = History::default();
+        history.record(submission(
+            "100",
+            Verdict::TooHigh,
+            1000,
+            Some(60),
+        ));
+        assert_eq!(
+            Err(Refusal::Wait {
+                remaining: Duration::from_secs(30)
+            }),
+            history.check(2024, 8, 1, "5", at(1030))
+        );
+        assert_eq!(
+            Ok(()),
+            history.check(2024, 8, 1, "5", at(1060))
+        );
+
+        history.record(submission(
+            "42",
+            Verdict::Correct,
+            2000,
+            None,
+        ));
+        assert_eq!(
+            Err(Refusal::AlreadySolved {
+                answer: "42".to_string()
+            }),
+            history.check(2024, 7, 1, "43", at(3000))
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() -> miette::Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "aoc-history-{}.toml",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let mut history = History::load(&path)?;
+        assert!(history.submissions().is_empty());
+        history.record(submission(
+            "100",
+            Verdict::TooHigh,
+            5,
+            Some(60),
+        ));
+        history.record(submission(
+            "42",
+            Verdict::Correct,
+            9,
+            None,
+        ));
+        history.save(&path)?;
+
+        let loaded = History::load(&path)?;
+        assert_eq!(
+            history.submissions(),
+            loaded.submissions()
+        );
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("verdict = \"too-high\""));
+
+        fs::remove_file(path).unwrap();
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+mod client;
+mod error;
+pub mod examples;
+pub mod history;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod puzzle;
+pub mod submit;
+
+pub use client::*;
+pub use error::ClientError;
@@ -0,0 +1,100 @@
+//! A local stand-in for adventofcode.com that
+//! tests of the client and its callers talk to.
+
+use std::{
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use tiny_http::{Response, Server};
+
+use crate::Client;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    pub method: String,
+    pub url: String,
+    pub body: String,
+}
+
+pub struct MockServer {
+    pub url: String,
+    pub requests: Arc<Mutex<Vec<Recorded>>>,
+    handle: JoinHandle<()>,
+}
+
+/// Serves `responses` in order, one per request,
+/// then shuts down.
+pub fn mock_server(
+    responses: Vec<(u16, &'static str)>,
+) -> MockServer {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}",
+        server.server_addr().to_ip().unwrap()
+    );
+    let requests = Arc::new(Mutex::new(vec![]));
+    let recorded = requests.clone();
+
+    let handle = thread::spawn(move || {
+        for (status, body) in responses {
+            let Ok(mut request) = server.recv() else {
+                return;
+            };
+            assert!(request.headers().iter().any(
+                |header| {
+                    header.field.equiv("Cookie")
+                        && header.value == "session=abc"
+                }
+            ));
+
+            let mut request_body = String::new();
+            request
+                .as_reader()
+                .read_to_string(&mut request_body)
+                .unwrap();
+            recorded.lock().unwrap().push(Recorded {
+                method: request.method().to_string(),
+                url: request.url().to_string(),
+                body: request_body,
+            });
+
+            request
+                .respond(
+                    Response::from_string(body)
+                        .with_status_code(status),
+                )
+                .unwrap();
+        }
+    });
+
+    MockServer {
+        url,
+        requests,
+        handle,
+    }
+}
+
+impl MockServer {
+    /// A client for this server with no delay
+    /// between requests.
+    pub fn client(&self) -> Client {
+        Client::new("abc")
+            .with_base_url(&self.url)
+            .with_min_delay(Duration::ZERO)
+    }
+
+    pub fn hits(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    /// Waits for every response to be served.
+    pub fn finish(self) -> Vec<Recorded> {
+        self.handle.join().unwrap();
+        Arc::try_unwrap(self.requests)
+            .unwrap()
+            .into_inner()
+            .unwrap()
+    }
+}
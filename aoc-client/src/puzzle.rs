@@ -0,0 +1,88 @@
+use crate::ClientError;
+
+/// The `<article class="day-desc">` sections of a
+/// puzzle page, one per unlocked part.
+pub fn articles(html: &str) -> Vec<&str> {
+    elements(html, "<article", "</article>")
+}
+
+/// Every slice of `html` that starts with `open`
+/// and ends with the next `close`, inclusive.
+pub(crate) fn elements<'a>(
+    html: &'a str,
+    open: &str,
+    close: &str,
+) -> Vec<&'a str> {
+    let mut elements = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(open) {
+        let Some(len) = rest[start..].find(close) else {
+            break;
+        };
+        let end = start + len + close.len();
+        elements.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    elements
+}
+
+/// Converts the puzzle description in a puzzle
+/// page to markdown.
+pub fn to_markdown(
+    html: &str,
+) -> Result<String, ClientError> {
+    let articles = articles(html);
+    if articles.is_empty() {
+        return Err(ClientError::MissingArticle);
+    }
+
+    Ok(articles
+        .iter()
+        .map(|article| html2md::parse_html(article))
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Some <em>text</em>.</p>
+<pre><code>3   4
+4   3
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>11</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>More.</p></article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_articles() {
+        let articles = articles(PAGE);
+        assert_eq!(2, articles.len());
+        assert!(articles[0].contains("Day 1"));
+        assert!(articles[1].contains("Part Two"));
+    }
+
+    #[test]
+    fn test_to_markdown() -> miette::Result<()> {
+        let markdown = to_markdown(PAGE)?;
+        assert!(markdown.contains("--- Day 1: Test ---"));
+        assert!(markdown.contains("*text*"));
+        assert!(markdown.contains("3   4\n4   3"));
+        assert!(markdown.contains("--- Part Two ---"));
+        assert!(!markdown.contains("Your puzzle answer"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_markdown_without_article() {
+        assert!(matches!(
+            to_markdown("<html>Please log in</html>"),
+            Err(ClientError::MissingArticle)
+        ));
+    }
+}
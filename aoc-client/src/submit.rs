@@ -0,0 +1,230 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{examples::text, puzzle::elements};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which way.
+    Incorrect,
+    /// Submitted too soon after the previous
+    /// answer, so it was not checked.
+    RateLimited,
+    /// The part was already solved or is not
+    /// unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Whether the site checked the answer and
+    /// rejected it.
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh
+                | Verdict::TooLow
+                | Verdict::Incorrect
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmitResponse {
+    pub verdict: Verdict,
+    /// How long the site asks us to wait before
+    /// the next submission.
+    pub wait: Option<Duration>,
+    /// The response's message as plain text, on
+    /// one line.
+    pub message: String,
+}
+
+/// Reads the verdict out of the page returned
+/// after posting an answer.
+pub fn parse_response(html: &str) -> SubmitResponse {
+    let article = elements(html, "<article", "</article>")
+        .first()
+        .copied()
+        .unwrap_or(html);
+    let message = text(article)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let verdict = if message
+        .contains("That's the right answer")
+    {
+        Verdict::Correct
+    } else if message
+        .contains("That's not the right answer")
+    {
+        if message.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if message.contains("your answer is too low")
+        {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else if message
+        .contains("You gave an answer too recently")
+    {
+        Verdict::RateLimited
+    } else if message.contains(
+        "You don't seem to be solving the right level",
+    ) {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown
+    };
+
+    SubmitResponse {
+        verdict,
+        wait: parse_wait(&message),
+        message,
+    }
+}
+
+/// Understands both "You have 1m 5s left to wait"
+/// and "please wait 5 minutes before trying
+/// again".
+fn parse_wait(message: &str) -> Option<Duration> {
+    if let Some(end) = message.find(" left to wait") {
+        let start = message[..end].rfind("You have ")? + 9;
+        return message[start..end].split(' ').try_fold(
+            Duration::ZERO,
+            |total, part| {
+                let (value, unit) =
+                    part.split_at(part.find(
+                        |c: char| !c.is_ascii_digit(),
+                    )?);
+                let value: u64 = value.parse().ok()?;
+                let seconds = match unit {
+                    "s" => value,
+                    "m" => value * 60,
+                    "h" => value * 60 * 60,
+                    _ => return None,
+                };
+                Some(total + Duration::from_secs(seconds))
+            },
+        );
+    }
+
+    let start = ["Please wait ", "please wait "]
+        .iter()
+        .find_map(|prefix| message.find(prefix))?
+        + 12;
+    let mut words = message[start..].split(' ');
+    let minutes = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    words
+        .next()?
+        .starts_with("minute")
+        .then(|| Duration::from_secs(minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!("<html><main>\n<article><p>{message}</p></article>\n</main></html>")
+    }
+
+    #[test]
+    fn test_correct() {
+        let response = parse_response(&page(
+            "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to finding the Chief Historian. <a href=\"/2024/day/7#part2\">[Continue to Part Two]</a>",
+        ));
+        assert_eq!(Verdict::Correct, response.verdict);
+        assert_eq!(None, response.wait);
+        assert!(response
+            .message
+            .contains("one gold star closer"));
+    }
+
+    #[test]
+    fn test_too_high_and_low() {
+        let response = parse_response(&page(
+            "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2024/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2024/day/7\">[Return to Day 7]</a>",
+        ));
+        assert_eq!(Verdict::TooHigh, response.verdict);
+        assert_eq!(
+            Some(Duration::from_secs(60)),
+            response.wait
+        );
+
+        let response = parse_response(&page(
+            "That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.",
+        ));
+        assert_eq!(Verdict::TooLow, response.verdict);
+        assert_eq!(
+            Some(Duration::from_secs(300)),
+            response.wait
+        );
+
+        let response = parse_response(&page(
+            "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+        ));
+        assert_eq!(Verdict::Incorrect, response.verdict);
+        assert!(response.verdict.is_wrong());
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        let response = parse_response(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 37s left to wait. <a href=\"/2024/day/7\">[Return to Day 7]</a>",
+        ));
+        assert_eq!(Verdict::RateLimited, response.verdict);
+        assert_eq!(
+            Some(Duration::from_secs(97)),
+            response.wait
+        );
+        assert!(!response.verdict.is_wrong());
+    }
+
+    #[test]
+    fn test_wrong_level_and_unknown() {
+        let response = parse_response(&page(
+            "You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2024/day/7\">[Return to Day 7]</a>",
+        ));
+        assert_eq!(Verdict::WrongLevel, response.verdict);
+
+        let response = parse_response("<html>Oops</html>");
+        assert_eq!(Verdict::Unknown, response.verdict);
+        assert_eq!("Oops", response.message);
+    }
+
+    #[test]
+    fn test_entities() {
+        let response = parse_response(&page(
+            "That&#39;s not the right answer; your answer is too low.  Try &quot;5 &amp; 6&quot;.",
+        ));
+        assert_eq!(Verdict::TooLow, response.verdict);
+        assert_eq!(
+            "That's not the right answer; your answer is too low. Try \"5 & 6\".",
+            response.message
+        );
+    }
+}
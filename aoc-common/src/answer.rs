@@ -0,0 +1,170 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// What a part's `process` solved the puzzle to.
+///
+/// Most answers are integers, so they are kept as
+/// numbers and compare numerically. Anything
+/// else, like a comma separated list, is `Text`.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Answer {
+    pub fn as_number(&self) -> Option<i128> {
+        match self {
+            Answer::Number(n) => Some(*n),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(
+    u8, u16, u32, u64, usize, i8, i16, i32, i64, isize,
+    i128
+);
+
+/// Beyond `i128::MAX` the number is kept as
+/// `Text`, which is also how `FromStr` reads it
+/// back.
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(
+            |_| Answer::Text(n.to_string()),
+            Answer::Number,
+        )
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// Reads a recorded answer back, e.g. from
+/// `answers.toml`. Only text that an integer
+/// would print as becomes a `Number`, so `"007"`
+/// stays `Text`.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse::<i128>() {
+            Ok(n) if n.to_string() == s => {
+                Answer::Number(n)
+            }
+            _ => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            Answer::Number(n) => n.to_string() == other,
+            Answer::Text(text) => text == other,
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<Answer> for &str {
+    fn eq(&self, other: &Answer) -> bool {
+        other == *self
+    }
+}
+
+impl PartialEq<Answer> for str {
+    fn eq(&self, other: &Answer) -> bool {
+        other == self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compares_with_str() {
+        assert_eq!("11", Answer::from(11u64));
+        assert_eq!(Answer::from(-3i32), "-3");
+        assert_ne!(Answer::from(11usize), "011");
+        assert_eq!("a,b", Answer::from("a,b"));
+        assert_eq!(
+            u128::MAX.to_string().as_str(),
+            Answer::from(u128::MAX)
+        );
+        assert_eq!(
+            u128::MAX
+                .to_string()
+                .parse::<Answer>()
+                .unwrap(),
+            Answer::from(u128::MAX)
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            Answer::Number(42),
+            "42".parse::<Answer>().unwrap()
+        );
+        assert_eq!(
+            Answer::Text("007".to_string()),
+            "007".parse::<Answer>().unwrap()
+        );
+        assert_eq!(
+            Answer::Text("1,2".to_string()),
+            "1,2".parse::<Answer>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_orders_numerically() {
+        assert!(Answer::from(9u8) < Answer::from(10u64));
+        assert_eq!(
+            Some(10),
+            Answer::from(10u64).as_number()
+        );
+        assert_eq!(None, Answer::from("10").as_number());
+    }
+}
@@ -0,0 +1,284 @@
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use nom_supreme::error::{
+    BaseErrorKind, ErrorTree, Expectation, StackContext,
+};
+use thiserror::Error;
+
+/// `nom::IResult` with an `ErrorTree`, so
+/// failures keep track of where they happened.
+pub type IResult<'a, O> =
+    nom::IResult<&'a str, O, ErrorTree<&'a str>>;
+
+/// A parse failure pointing at the offending part
+/// of the puzzle input.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub struct ParseError(Box<Spanned>);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to parse {name}")]
+#[diagnostic(code(aoc::parse_error))]
+struct Spanned {
+    name: String,
+    #[source_code]
+    src: NamedSource<String>,
+    #[label("{label}")]
+    span: SourceSpan,
+    label: String,
+    #[help]
+    help: Option<String>,
+}
+
+impl ParseError {
+    /// An error labelled `label` covering `len`
+    /// bytes of `input` from `offset`.
+    pub fn new(
+        input: &str,
+        offset: usize,
+        len: usize,
+        label: impl Into<String>,
+    ) -> Self {
+        let offset = offset.min(input.len());
+        Self(Box::new(Spanned {
+            name: "input".to_string(),
+            src: NamedSource::new(
+                "input",
+                input.to_string(),
+            ),
+            span: (offset, len.min(input.len() - offset))
+                .into(),
+            label: label.into(),
+            help: None,
+        }))
+    }
+
+    /// Like `new`, covering the character at
+    /// `offset`.
+    pub fn at(
+        input: &str,
+        offset: usize,
+        label: impl Into<String>,
+    ) -> Self {
+        let len = input
+            .get(offset..)
+            .and_then(|rest| rest.chars().next())
+            .map_or(0, char::len_utf8);
+        Self::new(input, offset, len, label)
+    }
+
+    /// Renames what failed to parse, e.g. `grid`.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.0.name = name.to_string();
+        self
+    }
+
+    pub fn with_help(
+        mut self,
+        help: impl Into<String>,
+    ) -> Self {
+        self.0.help = Some(help.into());
+        self
+    }
+
+    /// Converts a failed parse of `input`,
+    /// pointing at the furthest position any
+    /// branch of the parser reached.
+    pub fn from_nom(
+        input: &str,
+        err: nom::Err<ErrorTree<&str>>,
+    ) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => Self::at(
+                input,
+                input.len(),
+                "unexpected end of input",
+            ),
+            nom::Err::Error(tree)
+            | nom::Err::Failure(tree) => {
+                let (location, label, context) =
+                    furthest(&tree);
+                let error = Self::at(
+                    input,
+                    offset(input, location),
+                    label,
+                );
+                match context {
+                    Some(context) => error.with_help(
+                        format!("while parsing {context}"),
+                    ),
+                    None => error,
+                }
+            }
+        }
+    }
+
+    /// Where the span starts in the input.
+    pub fn offset(&self) -> usize {
+        self.0.span.offset()
+    }
+
+    pub fn label(&self) -> &str {
+        &self.0.label
+    }
+}
+
+/// The byte offset of `rest` within `input`,
+/// where `rest` is a suffix that a parser left
+/// over.
+pub fn offset(input: &str, rest: &str) -> usize {
+    (rest.as_ptr() as usize)
+        .saturating_sub(input.as_ptr() as usize)
+        .min(input.len())
+}
+
+/// Runs `parser` over the whole of `input`. Only
+/// trailing whitespace may be left unparsed.
+pub fn parse_all<'a, O, P>(
+    input: &'a str,
+    mut parser: P,
+) -> Result<O, ParseError>
+where
+    P: nom::Parser<&'a str, O, ErrorTree<&'a str>>,
+{
+    let (rest, output) = parser
+        .parse(input)
+        .map_err(|e| ParseError::from_nom(input, e))?;
+
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        return Ok(output);
+    }
+    let line = rest.lines().next().unwrap_or(rest);
+    Err(ParseError::new(
+        input,
+        offset(input, rest),
+        line.len(),
+        "unexpected input",
+    ))
+}
+
+/// The location, label and innermost context of
+/// the branch of `tree` that got furthest.
+pub fn furthest<'a>(
+    tree: &ErrorTree<&'a str>,
+) -> (&'a str, String, Option<&'static str>) {
+    match tree {
+        ErrorTree::Base { location, kind } => {
+            let label = match kind {
+                BaseErrorKind::Expected(
+                    Expectation::Eof,
+                ) => "expected end of input".to_string(),
+                BaseErrorKind::Expected(
+                    Expectation::Something,
+                ) => "unexpected end of input".to_string(),
+                BaseErrorKind::Expected(
+                    Expectation::CrLf,
+                ) => "expected a line break".to_string(),
+                BaseErrorKind::Kind(
+                    nom::error::ErrorKind::Eof,
+                ) => "unexpected end of input".to_string(),
+                // plain nom parsers only say which
+                // combinator gave up
+                BaseErrorKind::Kind(_) => {
+                    "unexpected input".to_string()
+                }
+                kind => kind.to_string(),
+            };
+            (*location, label, None)
+        }
+        ErrorTree::Stack { base, contexts } => {
+            let (location, label, context) = furthest(base);
+            let context = context.or_else(|| {
+                contexts.iter().find_map(|(_, context)| {
+                    match context {
+                        StackContext::Context(context) => {
+                            Some(*context)
+                        }
+                        StackContext::Kind(_) => None,
+                    }
+                })
+            });
+            (location, label, context)
+        }
+        ErrorTree::Alt(branches) => branches
+            .iter()
+            .map(furthest)
+            .min_by_key(|(location, ..)| location.len())
+            .unwrap_or((
+                "",
+                "no alternative matched".to_string(),
+                None,
+            )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        branch::alt,
+        character::complete::{self, line_ending},
+        combinator::recognize,
+        multi::separated_list1,
+        sequence::{pair, separated_pair},
+    };
+    use nom_supreme::{tag::complete::tag, ParserExt};
+
+    use super::*;
+
+    fn pairs(input: &str) -> IResult<Vec<(u32, u32)>> {
+        separated_list1(
+            line_ending,
+            separated_pair(
+                complete::u32,
+                tag("|"),
+                complete::u32,
+            )
+            .context("rule"),
+        )(input)
+    }
+
+    #[test]
+    fn test_points_at_failure() {
+        let input = "1|2\n3|4\n";
+        assert_eq!(
+            vec![(1, 2), (3, 4)],
+            parse_all(input, pairs).unwrap()
+        );
+
+        let error = parse_all("x|2", pairs).unwrap_err();
+        assert_eq!(0, error.offset());
+        assert_eq!(
+            "expected an ascii digit",
+            error.label()
+        );
+        assert_eq!(
+            Some("while parsing rule".to_string()),
+            error.0.help
+        );
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let input = "1|2\n3-4\n";
+        let error = parse_all(input, pairs).unwrap_err();
+        assert_eq!(4, error.offset());
+        assert_eq!("unexpected input", error.label());
+        assert_eq!(3, error.0.span.len());
+    }
+
+    #[test]
+    fn test_furthest_alternative() {
+        let input = "ab!";
+        let error = parse_all(
+            input,
+            alt((
+                tag("abc"),
+                recognize(pair(tag("a"), tag("bd"))),
+            )),
+        )
+        .unwrap_err();
+        assert_eq!(1, error.offset());
+        assert_eq!("expected \"bd\"", error.label());
+    }
+}
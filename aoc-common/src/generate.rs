@@ -0,0 +1,56 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The shape of a day's random input, and how to
+/// write one.
+pub trait Generator: Sized {
+    /// The shape for an input `size` big, by
+    /// whatever best measures how big the day's
+    /// input is.
+    fn sized(size: usize) -> Self;
+
+    fn generate_with(&self, rng: &mut impl Rng) -> String;
+
+    /// An input `size` big, the same every time
+    /// for the same `seed`.
+    fn generate(seed: u64, size: usize) -> String {
+        Self::sized(size)
+            .generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Digits(usize);
+
+    impl Generator for Digits {
+        fn sized(size: usize) -> Self {
+            Self(size)
+        }
+
+        fn generate_with(
+            &self,
+            rng: &mut impl Rng,
+        ) -> String {
+            (0..self.0)
+                .map(|_| {
+                    char::from(b'0' + rng.gen_range(0..10))
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_generate_is_seeded() {
+        assert_eq!(
+            Digits::generate(7, 50),
+            Digits::generate(7, 50)
+        );
+        assert_ne!(
+            Digits::generate(7, 50),
+            Digits::generate(8, 50)
+        );
+        assert_eq!(50, Digits::generate(7, 50).len());
+    }
+}
@@ -0,0 +1,7 @@
+pub mod answer;
+pub mod error;
+pub mod generate;
+#[cfg(feature = "proptest")]
+pub mod property;
+
+pub use answer::Answer;
@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+
+use miette::Diagnostic;
+use proptest::{
+    strategy::Strategy,
+    test_runner::{
+        Config, TestCaseError, TestError, TestRunner,
+    },
+};
+use thiserror::Error;
+
+/// A property that stopped holding, with the
+/// smallest input proptest could shrink it to.
+#[derive(Debug, Error, Diagnostic)]
+pub enum PropertyError {
+    #[error("`{name}` fails on {}: {reason}", path.display())]
+    #[diagnostic(
+        code(aoc::property),
+        help("the input is kept there and checked first on every run, delete it once fixed")
+    )]
+    Failed {
+        name: String,
+        path: PathBuf,
+        reason: String,
+    },
+    #[error("`{name}` gave up: {reason}")]
+    #[diagnostic(code(aoc::property))]
+    Aborted { name: String, reason: String },
+    #[error("could not write {}", path.display())]
+    #[diagnostic(code(aoc::property))]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Where the failing input for `name` is kept,
+/// e.g. `day-07/fixtures/solve.repro.txt`.
+pub fn reproducer_path(
+    manifest_dir: &str,
+    name: &str,
+) -> PathBuf {
+    Path::new(manifest_dir)
+        .join("fixtures")
+        .join(format!("{name}.repro.txt"))
+}
+
+/// Checks `property` against puzzle inputs from
+/// `inputs`, after any reproducer a previous run
+/// saved for `name`.
+///
+/// A failing input is shrunk and written to
+/// `reproducer_path`, so it can be run through
+/// `aoc run --input` as well.
+///
+/// `PROPTEST_CASES` sets how many inputs to try.
+pub fn check(
+    manifest_dir: &str,
+    name: &str,
+    inputs: impl Strategy<Value = String>,
+    property: impl Fn(&str) -> Result<(), TestCaseError>,
+) -> Result<(), PropertyError> {
+    let path = reproducer_path(manifest_dir, name);
+    if let Ok(input) = std::fs::read_to_string(&path) {
+        if let Err(error) = property(&input) {
+            return Err(PropertyError::Failed {
+                name: name.to_string(),
+                path,
+                reason: error.to_string(),
+            });
+        }
+    }
+
+    let mut runner = TestRunner::new(Config {
+        failure_persistence: None,
+        ..Config::default()
+    });
+    match runner.run(&inputs, |input| property(&input)) {
+        Ok(()) => Ok(()),
+        Err(TestError::Fail(reason, input)) => {
+            let written = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|()| {
+                    std::fs::write(&path, input)
+                });
+            if let Err(source) = written {
+                return Err(PropertyError::Io {
+                    path,
+                    source,
+                });
+            }
+            Err(PropertyError::Failed {
+                name: name.to_string(),
+                path,
+                reason: reason.to_string(),
+            })
+        }
+        Err(TestError::Abort(reason)) => {
+            Err(PropertyError::Aborted {
+                name: name.to_string(),
+                reason: reason.to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{
+        collection::vec, prop_assert, strategy::Just,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let dir = std::env::temp_dir().join(format!(
+            "aoc-property-{}",
+            std::process::id()
+        ));
+        let dir = dir.to_str().unwrap();
+        let inputs =
+            vec(0..100u32, 1..20).prop_map(|numbers| {
+                numbers
+                    .iter()
+                    .map(|n| format!("{n}\n"))
+                    .collect::<Vec<_>>()
+                    .concat()
+            });
+        let below_50 = |input: &str| {
+            prop_assert!(input
+                .lines()
+                .all(|n| n.parse::<u32>().unwrap() < 50));
+            Ok(())
+        };
+
+        assert!(check(
+            dir,
+            "always",
+            inputs.clone(),
+            |_| Ok(())
+        )
+        .is_ok());
+
+        let error = check(
+            dir,
+            "below_50",
+            inputs.clone(),
+            below_50,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            PropertyError::Failed { .. }
+        ));
+        let path = reproducer_path(dir, "below_50");
+        assert_eq!(
+            "50\n",
+            std::fs::read_to_string(&path).unwrap()
+        );
+
+        // The saved input fails again straight away,
+        // even when nothing generated would.
+        let error = check(
+            dir,
+            "below_50",
+            Just("1\n".to_string()),
+            below_50,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            PropertyError::Failed { .. }
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
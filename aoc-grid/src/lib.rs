@@ -0,0 +1,471 @@
+use std::{fmt, str::FromStr};
+
+use aoc_common::error::{offset, ParseError};
+use glam::IVec2;
+
+/// Up, right, down and left, in clockwise order
+/// starting from up (`y` grows downwards).
+pub const ORTHOGONAL: [IVec2; 4] =
+    [IVec2::NEG_Y, IVec2::X, IVec2::Y, IVec2::NEG_X];
+
+/// The four diagonals, clockwise from up-right.
+pub const DIAGONAL: [IVec2; 4] = [
+    IVec2::new(1, -1),
+    IVec2::new(1, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+/// All eight directions, clockwise from up.
+pub const ALL_DIRECTIONS: [IVec2; 8] = [
+    IVec2::NEG_Y,
+    IVec2::new(1, -1),
+    IVec2::X,
+    IVec2::new(1, 1),
+    IVec2::Y,
+    IVec2::new(-1, 1),
+    IVec2::NEG_X,
+    IVec2::new(-1, -1),
+];
+
+/// A dense, row-major 2D grid addressed with
+/// `IVec2` positions where `x` is the column and
+/// `y` is the row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid<char> {
+    /// Parses every character of every line as a
+    /// cell.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, Some)
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses a grid, converting each character
+    /// with `cell`. Returning `None` rejects the
+    /// character. Trailing whitespace, like the
+    /// newline at the end of a file, is ignored.
+    pub fn parse_with<F>(
+        input: &str,
+        mut cell: F,
+    ) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for line in input.trim_end().lines() {
+            let start = cells.len();
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::at(
+                        input,
+                        offset(input, &line[i..]),
+                        format!("unexpected `{c}`"),
+                    )
+                    .with_name("grid")
+                })?;
+                cells.push(value);
+            }
+
+            let found = cells.len() - start;
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(ParseError::new(
+                    input,
+                    offset(input, line),
+                    line.len(),
+                    format!(
+                        "row is {found} cells wide, expected {expected}"
+                    ),
+                )
+                .with_name("grid")
+                .with_help(
+                    "every row of a grid must have the same width",
+                ));
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                cells,
+                width,
+                height,
+            }),
+            _ => Err(ParseError::at(
+                input,
+                0,
+                "grid input is empty",
+            )
+            .with_name("grid")),
+        }
+    }
+
+    /// Builds a `width` by `height` grid by
+    /// calling `f` for each position in
+    /// row-major order.
+    pub fn from_fn<F>(
+        width: usize,
+        height: usize,
+        mut f: F,
+    ) -> Self
+    where
+        F: FnMut(IVec2) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(IVec2::new(x as i32, y as i32)))
+            .collect();
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: IVec2) -> bool {
+        (0..self.width as i32).contains(&pos.x)
+            && (0..self.height as i32).contains(&pos.y)
+    }
+
+    fn index(&self, pos: IVec2) -> Option<usize> {
+        self.in_bounds(pos).then(|| {
+            pos.y as usize * self.width + pos.x as usize
+        })
+    }
+
+    fn position(&self, index: usize) -> IVec2 {
+        IVec2::new(
+            (index % self.width) as i32,
+            (index / self.width) as i32,
+        )
+    }
+
+    /// The cell at `pos`, or `None` when `pos` is
+    /// outside the grid.
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(
+        &mut self,
+        pos: IVec2,
+    ) -> Option<&mut T> {
+        self.index(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position and its cell, in row-major
+    /// order.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (self.position(i), value))
+    }
+
+    /// Every position within the grid, in
+    /// row-major order.
+    pub fn positions(
+        &self,
+    ) -> impl Iterator<Item = IVec2> + '_ {
+        (0..self.cells.len()).map(|i| self.position(i))
+    }
+
+    /// The in-bounds neighbours of `pos` in the
+    /// given `directions`.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: IVec2,
+        directions: &'a [IVec2],
+    ) -> impl Iterator<Item = (IVec2, &'a T)> + 'a {
+        directions.iter().filter_map(move |dir| {
+            let next = pos + *dir;
+            self.get(next).map(|value| (next, value))
+        })
+    }
+
+    /// The up to four orthogonal neighbours of
+    /// `pos`.
+    pub fn neighbours4(
+        &self,
+        pos: IVec2,
+    ) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.neighbours(pos, &ORTHOGONAL)
+    }
+
+    /// The up to eight orthogonal and diagonal
+    /// neighbours of `pos`.
+    pub fn neighbours8(
+        &self,
+        pos: IVec2,
+    ) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.neighbours(pos, &ALL_DIRECTIONS)
+    }
+
+    /// Walks from `pos` in steps of `dir` until
+    /// the edge of the grid. `pos` itself is
+    /// not yielded.
+    pub fn ray(
+        &self,
+        pos: IVec2,
+        dir: IVec2,
+    ) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        std::iter::successors(Some(pos), move |p| {
+            (dir != IVec2::ZERO).then_some(*p + dir)
+        })
+        .skip(1)
+        .map_while(|p| self.get(p).map(|value| (p, value)))
+    }
+
+    /// The first position, in row-major order,
+    /// whose cell matches `predicate`.
+    pub fn find<P>(&self, predicate: P) -> Option<IVec2>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.position(i))
+    }
+
+    /// Every position whose cell matches
+    /// `predicate`.
+    pub fn find_all<'a, P>(
+        &'a self,
+        mut predicate: P,
+    ) -> impl Iterator<Item = IVec2> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a,
+    {
+        self.iter()
+            .filter(move |(_, value)| predicate(value))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// The first position holding `value`, e.g.
+    /// `grid.position_of(&'^')`.
+    pub fn position_of(&self, value: &T) -> Option<IVec2> {
+        self.find(|cell| cell == value)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| {
+            self.cells[pos.x as usize * self.width
+                + pos.y as usize]
+                .clone()
+        })
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| {
+            let x = pos.y;
+            let y = self.height as i32 - 1 - pos.x;
+            self.get(IVec2::new(x, y))
+                .expect(
+                    "rotated position should be in bounds",
+                )
+                .clone()
+        })
+    }
+
+    /// Rotates a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| {
+            let x = self.width as i32 - 1 - pos.y;
+            let y = pos.x;
+            self.get(IVec2::new(x, y))
+                .expect(
+                    "rotated position should be in bounds",
+                )
+                .clone()
+        })
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        // `from_fn` allows an empty grid, which has
+        // no rows to chunk
+        if self.width == 0 {
+            return Ok(());
+        }
+        for row in self.cells.chunks(self.width) {
+            writeln!(
+                f,
+                "{}",
+                row.iter().collect::<String>()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "ab.
+.#c
+";
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT)?;
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(Some(&'#'), grid.get(IVec2::new(1, 1)));
+        assert_eq!(Some(&'c'), grid.get(IVec2::new(2, 1)));
+        assert_eq!(None, grid.get(IVec2::new(3, 0)));
+        assert_eq!(None, grid.get(IVec2::new(0, -1)));
+
+        let padded = Grid::parse("ab\r\ncd \n\n")?;
+        assert_eq!(
+            (2, 2),
+            (padded.width(), padded.height())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Grid::parse("").unwrap_err();
+        assert_eq!("grid input is empty", error.label());
+        let error = Grid::parse(" \n").unwrap_err();
+        assert_eq!("grid input is empty", error.label());
+
+        let error = Grid::parse("abc\nab").unwrap_err();
+        assert_eq!(4, error.offset());
+        assert_eq!(
+            "row is 2 cells wide, expected 3",
+            error.label()
+        );
+
+        let error = Grid::parse_with("..\n.x", |c| {
+            (c == '.').then_some(0)
+        })
+        .unwrap_err();
+        assert_eq!(4, error.offset());
+        assert_eq!("unexpected `x`", error.label());
+    }
+
+    #[test]
+    fn test_neighbours() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT)?;
+        let corner: Vec<_> =
+            grid.neighbours4(IVec2::ZERO).collect();
+        assert_eq!(
+            vec![(IVec2::X, &'b'), (IVec2::Y, &'.')],
+            corner
+        );
+        assert_eq!(
+            3,
+            grid.neighbours8(IVec2::ZERO).count()
+        );
+        assert_eq!(
+            5,
+            grid.neighbours8(IVec2::new(1, 0)).count()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ray() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT)?;
+        let ray: Vec<_> = grid
+            .ray(IVec2::ZERO, IVec2::X)
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!(vec!['b', '.'], ray);
+        assert_eq!(
+            0,
+            grid.ray(IVec2::ZERO, IVec2::ZERO).count()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_find() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT)?;
+        assert_eq!(
+            Some(IVec2::new(1, 1)),
+            grid.position_of(&'#')
+        );
+        assert_eq!(2, grid.find_all(|c| *c == '.').count());
+        assert_eq!(None, grid.position_of(&'^'));
+        Ok(())
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT)?;
+        assert_eq!(
+            "a.\nb#\n.c\n",
+            grid.transpose().to_string()
+        );
+        assert_eq!(
+            ".a\n#b\nc.\n",
+            grid.rotate_cw().to_string()
+        );
+        assert_eq!(
+            ".c\nb#\na.\n",
+            grid.rotate_ccw().to_string()
+        );
+        assert_eq!(grid, grid.rotate_cw().rotate_ccw());
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_empty() {
+        let grid = Grid::from_fn(0, 3, |_| '.');
+        assert_eq!("", grid.to_string());
+        assert_eq!("", grid.transpose().to_string());
+    }
+}
@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+
+use aoc_client::examples::Example;
+use miette::{IntoDiagnostic, WrapErr};
+
+use crate::input::workspace_root;
+
+/// Where a day keeps its example inputs and
+/// answers, e.g. `day-07/fixtures`.
+pub fn fixtures_dir(day: &str) -> PathBuf {
+    workspace_root().join(day).join("fixtures")
+}
+
+/// The puzzle page saved by `just get-puzzle`.
+pub fn puzzle_html_path(day: &str) -> PathBuf {
+    workspace_root().join(day).join("puzzle.html")
+}
+
+/// Writes each example as
+/// `partN.example.txt` and, when the answer was
+/// found, `partN.answer.txt`. Returns the written
+/// paths.
+pub fn write_examples(
+    dir: &Path,
+    examples: &[Example],
+) -> miette::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)
+        .into_diagnostic()
+        .wrap_err_with(|| {
+            format!("creating `{}`", dir.display())
+        })?;
+
+    let mut written = vec![];
+    for example in examples {
+        let mut files = vec![(
+            format!("part{}.example.txt", example.part),
+            example.input.as_str(),
+        )];
+        if let Some(answer) = &example.answer {
+            files.push((
+                format!("part{}.answer.txt", example.part),
+                answer.as_str(),
+            ));
+        }
+
+        for (name, contents) in files {
+            let path = dir.join(name);
+            std::fs::write(&path, contents)
+                .into_diagnostic()
+                .wrap_err_with(|| {
+                    format!("writing `{}`", path.display())
+                })?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_examples() -> miette::Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "aoc-fixtures-{}",
+            std::process::id()
+        ));
+        let written = write_examples(
+            &dir,
+            &[
+                Example {
+                    part: 1,
+                    input: "125 17\n".to_string(),
+                    answer: Some("55312".to_string()),
+                },
+                Example {
+                    part: 2,
+                    input: "125 17\n".to_string(),
+                    answer: None,
+                },
+            ],
+        )?;
+
+        assert_eq!(3, written.len());
+        assert_eq!(
+            "55312",
+            std::fs::read_to_string(
+                dir.join("part1.answer.txt")
+            )
+            .unwrap()
+        );
+        assert!(dir.join("part2.example.txt").exists());
+        assert!(!dir.join("part2.answer.txt").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+}
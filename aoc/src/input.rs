@@ -0,0 +1,49 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use miette::{IntoDiagnostic, WrapErr};
+
+use crate::registry::Part;
+
+/// The workspace root, where every `day-NN`
+/// directory lives.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect(
+        "aoc crate should live inside the workspace",
+    )
+}
+
+/// The puzzle input a day's part reads when no
+/// `--input` is given, e.g. `day-07/input2.txt`.
+pub fn default_input_path(
+    day: &str,
+    part: Part,
+) -> PathBuf {
+    workspace_root()
+        .join(day)
+        .join(format!("input{}.txt", part.number()))
+}
+
+/// Reads the input at `path`, where `-` means
+/// stdin.
+pub fn read_input(path: &Path) -> miette::Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .into_diagnostic()
+            .wrap_err("reading input from stdin")?;
+        return Ok(input);
+    }
+
+    std::fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| {
+            format!(
+                "reading input from `{}`",
+                path.display()
+            )
+        })
+}
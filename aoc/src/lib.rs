@@ -0,0 +1,5 @@
+pub mod fixtures;
+pub mod input;
+pub mod registry;
+pub mod submit;
+pub mod verify;
@@ -0,0 +1,299 @@
+use std::{io::Write, path::PathBuf};
+
+use aoc::{
+    fixtures::{
+        fixtures_dir, puzzle_html_path, write_examples,
+    },
+    input::{default_input_path, read_input},
+    registry::{self, Part, Solution, SOLUTIONS},
+    submit::{history_path, submit},
+    verify::{verify, Answers},
+};
+use aoc_client::{Client, DEFAULT_YEAR};
+use aoc_common::Answer;
+use clap::{Parser, Subcommand};
+use miette::{miette, Context, IntoDiagnostic};
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List every registered day and part, and
+    /// whether it is still a `todo!()`
+    List,
+    /// Run a day's part against an input file
+    Run {
+        /// formatted as `day-01` to match all
+        /// other commands in the repo
+        day: String,
+        part: Part,
+        /// defaults to the day's `inputN.txt`,
+        /// use `-` to read from stdin
+        #[clap(short, long)]
+        input: Option<PathBuf>,
+        /// print the part's debugging explanation
+        /// instead of the answer, showing at most
+        /// this many entries per item
+        #[clap(long, num_args = 0..=1, value_name = "LIMIT")]
+        explain: Option<Option<usize>>,
+    },
+    /// Run a day's part and post its answer to
+    /// adventofcode.com
+    Submit {
+        day: String,
+        part: Part,
+        /// defaults to the day's `inputN.txt`
+        #[clap(short, long)]
+        input: Option<PathBuf>,
+        #[clap(short, long, env = "AOC_YEAR", default_value_t = DEFAULT_YEAR)]
+        year: u32,
+        /// don't ask before posting
+        #[clap(long)]
+        yes: bool,
+    },
+    /// Run every implemented part against its
+    /// real input and compare with the
+    /// confirmed answers
+    Verify {
+        /// only verify this day, e.g. `day-07`
+        day: Option<String>,
+        /// defaults to `answers.toml` in the
+        /// workspace root
+        #[clap(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Write a random but valid input for a day,
+    /// for stress testing and benchmarks
+    Generate {
+        day: String,
+        #[clap(long, default_value_t = 0)]
+        seed: u64,
+        /// what this counts depends on the day,
+        /// e.g. lines for day-07 and obstacles
+        /// for day-06
+        #[clap(long, default_value_t = 100)]
+        size: usize,
+        /// defaults to stdout
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Extract the example inputs and answers
+    /// from a saved puzzle page into the
+    /// day's `fixtures` directory
+    Examples {
+        day: String,
+        /// defaults to the day's `puzzle.html`,
+        /// as saved by `just get-puzzle`
+        #[clap(long)]
+        html: Option<PathBuf>,
+    },
+}
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    match Args::parse().command {
+        Command::List => {
+            for solution in SOLUTIONS {
+                let status = if solution.is_implemented() {
+                    "done"
+                } else {
+                    "todo"
+                };
+                println!(
+                    "{} {} {}",
+                    solution.day, solution.part, status
+                );
+            }
+        }
+        Command::Run {
+            day,
+            part,
+            input,
+            explain: Some(limit),
+        } => {
+            let explain = registry::find(&day, part)
+                .and_then(|solution| solution.explain)
+                .ok_or_else(|| {
+                    miette!(
+                        "{day} {part} has no explain mode"
+                    )
+                })?;
+            let path = input.unwrap_or_else(|| {
+                default_input_path(&day, part)
+            });
+            println!(
+                "{}",
+                explain(&read_input(&path)?, limit)?
+            );
+        }
+        Command::Run {
+            day,
+            part,
+            input,
+            explain: None,
+        } => {
+            let (_, result) = solve(&day, part, input)?;
+            println!("{}", result);
+        }
+        Command::Submit {
+            day,
+            part,
+            input,
+            year,
+            yes,
+        } => {
+            let (solution, answer) =
+                solve(&day, part, input)?;
+            let answer = answer.to_string();
+            println!("{day} {part}: {answer}");
+
+            let client = Client::from_env()?
+                .with_year(year)
+                .with_cache_dir(
+                    aoc::input::workspace_root()
+                        .join(".aoc-cache"),
+                );
+            let response = submit(
+                &client,
+                &history_path(),
+                solution,
+                &answer,
+                || {
+                    yes || confirm(&format!(
+                        "submit `{answer}`?"
+                    ))
+                },
+            )?;
+
+            if let Some(response) = response {
+                println!(
+                    "{:?}: {}",
+                    response.verdict, response.message
+                );
+                if let Some(wait) = response.wait {
+                    println!(
+                        "wait {}s before the next answer",
+                        wait.as_secs()
+                    );
+                }
+            }
+        }
+        Command::Verify { day, answers } => {
+            let answers = Answers::load(
+                &answers
+                    .unwrap_or_else(Answers::default_path),
+            )?;
+
+            let mut failures = 0;
+            for solution in
+                SOLUTIONS.iter().filter(|solution| {
+                    day.as_ref().is_none_or(|day| {
+                        solution.day == day
+                    })
+                })
+            {
+                let verification =
+                    verify(solution, &answers);
+                if verification.outcome.is_failure() {
+                    failures += 1;
+                }
+                println!("{verification}");
+            }
+
+            if failures > 0 {
+                return Err(miette!(
+                    "{failures} part(s) failed verification"
+                ));
+            }
+        }
+        Command::Generate {
+            day,
+            seed,
+            size,
+            output,
+        } => {
+            let generator = registry::find_generator(&day)
+                .ok_or_else(|| {
+                    miette!(
+                        "`{day}` has no input generator"
+                    )
+                })?;
+            let input = (generator.generate)(seed, size);
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, input)
+                        .into_diagnostic()
+                        .wrap_err_with(|| {
+                            format!(
+                                "writing `{}`",
+                                path.display()
+                            )
+                        })?;
+                    println!(
+                        "wrote {} with {size} {}",
+                        path.display(),
+                        generator.size
+                    );
+                }
+                None => print!("{input}"),
+            }
+        }
+        Command::Examples { day, html } => {
+            let path = html
+                .unwrap_or_else(|| puzzle_html_path(&day));
+            let html = std::fs::read_to_string(&path)
+                .into_diagnostic()
+                .wrap_err_with(|| {
+                    format!("reading `{}`", path.display())
+                })?;
+
+            let examples =
+                aoc_client::examples::extract(&html)?;
+            for path in write_examples(
+                &fixtures_dir(&day),
+                &examples,
+            )? {
+                println!("wrote {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs `day`'s `part` against `input`, or the
+/// day's own input file.
+fn solve(
+    day: &str,
+    part: Part,
+    input: Option<PathBuf>,
+) -> miette::Result<(&'static Solution, Answer)> {
+    let solution =
+        registry::find(day, part).ok_or_else(|| {
+            miette!("`{day}` has no registered {part}")
+        })?;
+    let process = solution.process.ok_or_else(|| {
+        miette!("{day} {part} is not implemented yet")
+    })?;
+
+    let path = input
+        .unwrap_or_else(|| default_input_path(day, part));
+    let file = read_input(&path)?;
+    let result = process(&file)
+        .with_context(|| format!("process {day} {part}"))?;
+    Ok((solution, result))
+}
+
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = std::io::stdout().flush();
+    let mut reply = String::new();
+    std::io::stdin().read_line(&mut reply).is_ok()
+        && reply.trim().eq_ignore_ascii_case("y")
+}
@@ -0,0 +1,352 @@
+use std::fmt;
+
+use aoc_common::{generate::Generator as _, Answer};
+use clap::ValueEnum;
+
+/// Signature shared by every
+/// `dayNN::partN::process`.
+pub type Process = fn(&str) -> miette::Result<Answer>;
+
+/// Optional debugging output for a part, given
+/// the input and how many entries to show per
+/// item (all when `None`).
+pub type Explain =
+    fn(&str, Option<usize>) -> miette::Result<String>;
+
+/// Signature of `Generator::generate` on every
+/// `dayNN::generate::Params`.
+pub type Generate = fn(u64, usize) -> String;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    ValueEnum,
+)]
+pub enum Part {
+    Part1,
+    Part2,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::Part1 => 1,
+            Part::Part2 => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "part{}", self.number())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub day: &'static str,
+    pub part: Part,
+    /// `None` while the part is still a `todo!()`
+    /// stub.
+    pub process: Option<Process>,
+    pub explain: Option<Explain>,
+}
+
+impl Solution {
+    pub const fn solved(
+        day: &'static str,
+        part: Part,
+        process: Process,
+    ) -> Self {
+        Self {
+            day,
+            part,
+            process: Some(process),
+            explain: None,
+        }
+    }
+
+    /// For a freshly generated day whose parts
+    /// are still stubs.
+    pub const fn todo(
+        day: &'static str,
+        part: Part,
+    ) -> Self {
+        Self {
+            day,
+            part,
+            process: None,
+            explain: None,
+        }
+    }
+
+    const fn explained(self, explain: Explain) -> Self {
+        Self {
+            explain: Some(explain),
+            ..self
+        }
+    }
+
+    pub fn is_implemented(&self) -> bool {
+        self.process.is_some()
+    }
+}
+
+pub const SOLUTIONS: &[Solution] = &[
+    Solution::solved(
+        "day-01",
+        Part::Part1,
+        day_01::part1::process,
+    ),
+    Solution::solved(
+        "day-01",
+        Part::Part2,
+        day_01::part2::process,
+    ),
+    Solution::solved(
+        "day-02",
+        Part::Part1,
+        day_02::part1::process,
+    )
+    .explained(day_02::part1::explain),
+    Solution::solved(
+        "day-02",
+        Part::Part2,
+        day_02::part2::process,
+    )
+    .explained(day_02::part2::explain),
+    Solution::solved(
+        "day-03",
+        Part::Part1,
+        day_03::part1::process,
+    )
+    .explained(day_03::part1::explain),
+    Solution::solved(
+        "day-03",
+        Part::Part2,
+        day_03::part2::process,
+    )
+    .explained(day_03::part2::explain),
+    Solution::solved(
+        "day-04",
+        Part::Part1,
+        day_04::part1::process,
+    ),
+    Solution::solved(
+        "day-04",
+        Part::Part2,
+        day_04::part2::process,
+    ),
+    Solution::solved(
+        "day-05",
+        Part::Part1,
+        day_05::part1::process,
+    ),
+    Solution::solved(
+        "day-05",
+        Part::Part2,
+        day_05::part2::process,
+    ),
+    Solution::solved(
+        "day-06",
+        Part::Part1,
+        day_06::part1::process,
+    ),
+    Solution::solved(
+        "day-06",
+        Part::Part2,
+        day_06::part2::process,
+    ),
+    Solution::solved(
+        "day-07",
+        Part::Part1,
+        day_07::part1::process,
+    )
+    .explained(day_07::part1::explain),
+    Solution::solved(
+        "day-07",
+        Part::Part2,
+        day_07::part2::process,
+    )
+    .explained(day_07::part2::explain),
+    Solution::solved(
+        "day-08",
+        Part::Part1,
+        day_08::part1::process,
+    )
+    .explained(day_08::part1::explain),
+    Solution::solved(
+        "day-08",
+        Part::Part2,
+        day_08::part2::process,
+    )
+    .explained(day_08::part2::explain),
+    Solution::solved(
+        "day-09",
+        Part::Part1,
+        day_09::part1::process,
+    ),
+    Solution::solved(
+        "day-09",
+        Part::Part2,
+        day_09::part2::process,
+    ),
+    Solution::solved(
+        "day-11",
+        Part::Part1,
+        day_11::part1::process,
+    ),
+    Solution::solved(
+        "day-11",
+        Part::Part2,
+        day_11::part2::process,
+    ),
+];
+
+/// A random but valid input for `day`, from a
+/// seed and a size whose meaning is up to the
+/// day, e.g. how many lines.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    pub day: &'static str,
+    /// What the size counts.
+    pub size: &'static str,
+    pub generate: Generate,
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator {
+        day: "day-01",
+        size: "lines",
+        generate: day_01::generate::Params::generate,
+    },
+    Generator {
+        day: "day-02",
+        size: "reports",
+        generate: day_02::generate::Params::generate,
+    },
+    Generator {
+        day: "day-03",
+        size: "fragments",
+        generate: day_03::generate::Params::generate,
+    },
+    Generator {
+        day: "day-04",
+        size: "grid side",
+        generate: day_04::generate::Params::generate,
+    },
+    Generator {
+        day: "day-05",
+        size: "updates",
+        generate: day_05::generate::Params::generate,
+    },
+    Generator {
+        day: "day-06",
+        size: "obstacles",
+        generate: day_06::generate::Params::generate,
+    },
+    Generator {
+        day: "day-07",
+        size: "lines",
+        generate: day_07::generate::Params::generate,
+    },
+    Generator {
+        day: "day-08",
+        size: "map side",
+        generate: day_08::generate::Params::generate,
+    },
+    Generator {
+        day: "day-09",
+        size: "disk map digits",
+        generate: day_09::generate::Params::generate,
+    },
+    Generator {
+        day: "day-11",
+        size: "stones",
+        generate: day_11::generate::Params::generate,
+    },
+];
+
+/// The puzzle day number in a day formatted as
+/// `day-01`.
+pub fn day_number(day: &str) -> Option<u32> {
+    day.strip_prefix("day-")?.parse().ok()
+}
+
+/// Looks up the registered solution for `day`
+/// (formatted as `day-01`) and `part`.
+pub fn find(
+    day: &str,
+    part: Part,
+) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| {
+        solution.day == day && solution.part == part
+    })
+}
+
+/// Looks up the input generator for `day`.
+pub fn find_generator(
+    day: &str,
+) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let solution = find("day-07", Part::Part2).unwrap();
+        assert!(solution.is_implemented());
+        assert!(solution.explain.is_some());
+        assert!(find("day-11", Part::Part2)
+            .unwrap()
+            .is_implemented());
+        assert!(!Solution::todo("day-12", Part::Part1)
+            .is_implemented());
+        assert!(find("day-10", Part::Part1).is_none());
+    }
+
+    #[test]
+    fn test_generators_match_solutions() {
+        for generator in GENERATORS {
+            let input = (generator.generate)(1, 10);
+            for part in [Part::Part1, Part::Part2] {
+                let process = find(generator.day, part)
+                    .and_then(|solution| solution.process)
+                    .unwrap();
+                assert!(
+                    process(&input).is_ok(),
+                    "{} {part}",
+                    generator.day
+                );
+            }
+        }
+        assert!(find_generator("day-10").is_none());
+    }
+
+    #[test]
+    fn test_day_number() {
+        assert_eq!(Some(7), day_number("day-07"));
+        assert_eq!(Some(11), day_number("day-11"));
+        assert_eq!(None, day_number("07"));
+        assert_eq!(None, day_number("day-x"));
+    }
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        assert!(SOLUTIONS.windows(2).all(|pair| {
+            (pair[0].day, pair[0].part)
+                < (pair[1].day, pair[1].part)
+        }));
+    }
+}
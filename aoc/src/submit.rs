@@ -0,0 +1,139 @@
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use aoc_client::{
+    history::{unix_seconds, History, Submission},
+    submit::SubmitResponse,
+    Client,
+};
+use miette::miette;
+
+use crate::{
+    input::workspace_root,
+    registry::{day_number, Solution},
+};
+
+/// Every answer posted from this checkout.
+pub fn history_path() -> PathBuf {
+    workspace_root().join(".aoc-history.toml")
+}
+
+/// Posts `answer` for `solution` unless the
+/// history already rules it out or `confirm`
+/// declines, then records the response.
+pub fn submit(
+    client: &Client,
+    history_path: &Path,
+    solution: &Solution,
+    answer: &str,
+    confirm: impl FnOnce() -> bool,
+) -> miette::Result<Option<SubmitResponse>> {
+    let day =
+        day_number(solution.day).ok_or_else(|| {
+            miette!(
+                "`{}` is not formatted as `day-01`",
+                solution.day
+            )
+        })?;
+    let part = solution.part.number();
+
+    let mut history = History::load(history_path)?;
+    history
+        .check(client.year(), day, part, answer, SystemTime::now())
+        .map_err(|refusal| {
+            miette!(
+                "not submitting `{answer}` for {} {}: {refusal}",
+                solution.day,
+                solution.part
+            )
+        })?;
+
+    if !confirm() {
+        return Ok(None);
+    }
+
+    let response = client.submit(day, part, answer)?;
+    history.record(Submission {
+        year: client.year(),
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict: response.verdict,
+        at: unix_seconds(SystemTime::now()),
+        wait: response.wait.map(|wait| wait.as_secs()),
+    });
+    history.save(history_path)?;
+
+    Ok(Some(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_client::{mock::mock_server, submit::Verdict};
+
+    use super::*;
+    use crate::registry::{find, Part};
+
+    #[test]
+    fn test_submit_records_and_refuses_known_wrong(
+    ) -> miette::Result<()> {
+        let server = mock_server(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        )]);
+        let client = server.client();
+        let path = std::env::temp_dir().join(format!(
+            "aoc-submit-history-{}.toml",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let solution = find("day-07", Part::Part1).unwrap();
+
+        let response = submit(
+            &client,
+            &path,
+            solution,
+            "500",
+            || true,
+        )?;
+        assert_eq!(
+            Some(Verdict::TooHigh),
+            response.map(|response| response.verdict)
+        );
+
+        // these must be refused before reaching the
+        // server
+        assert!(submit(
+            &client,
+            &path,
+            solution,
+            "500",
+            || true
+        )
+        .is_err());
+        assert!(submit(
+            &client,
+            &path,
+            solution,
+            "900",
+            || true
+        )
+        .is_err());
+        assert!(submit(
+            &client,
+            &path,
+            solution,
+            "400",
+            || false
+        )?
+        .is_none());
+
+        let history = History::load(&path)?;
+        assert_eq!(1, history.submissions().len());
+        assert_eq!(1, server.finish().len());
+        std::fs::remove_file(path).unwrap();
+        Ok(())
+    }
+}
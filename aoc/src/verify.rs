@@ -0,0 +1,370 @@
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc_common::Answer;
+use miette::{IntoDiagnostic, WrapErr};
+use serde::Deserialize;
+
+use crate::{
+    input::{default_input_path, workspace_root},
+    registry::{Part, Solution},
+};
+
+/// Confirmed answers for the real puzzle inputs,
+/// keyed by day (`day-01`) and then part.
+#[derive(Debug, Default, Deserialize)]
+pub struct Answers(BTreeMap<String, DayAnswers>);
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Answers {
+    /// The checked-in `answers.toml` at the
+    /// workspace root.
+    pub fn default_path() -> PathBuf {
+        workspace_root().join("answers.toml")
+    }
+
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .into_diagnostic()
+            .wrap_err_with(|| {
+                format!("reading `{}`", path.display())
+            })?;
+        Self::parse(&contents)
+    }
+
+    pub fn parse(input: &str) -> miette::Result<Self> {
+        toml::from_str(input)
+            .into_diagnostic()
+            .wrap_err("parsing answers")
+    }
+
+    pub fn get(
+        &self,
+        day: &str,
+        part: Part,
+    ) -> Option<&str> {
+        let day = self.0.get(day)?;
+        match part {
+            Part::Part1 => day.part1.as_deref(),
+            Part::Part2 => day.part2.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// The solution ran but there is no confirmed
+    /// answer to compare against yet.
+    MissingAnswer {
+        actual: Answer,
+    },
+    MissingInput {
+        path: PathBuf,
+    },
+    Todo,
+    Error(String),
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Fail { .. } | Outcome::Error(_)
+        )
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAIL expected {expected}, got {actual}")
+            }
+            Outcome::MissingAnswer { actual } => {
+                write!(f, "missing answer, got {actual}")
+            }
+            Outcome::MissingInput { path } => {
+                write!(
+                    f,
+                    "missing input `{}`",
+                    path.display()
+                )
+            }
+            Outcome::Todo => write!(f, "todo"),
+            Outcome::Error(e) => write!(f, "ERROR {e}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub solution: &'static Solution,
+    pub outcome: Outcome,
+    pub elapsed: Option<Duration>,
+}
+
+impl fmt::Display for Verification {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.solution.day,
+            self.solution.part,
+            self.outcome
+        )?;
+        if let Some(elapsed) = self.elapsed {
+            write!(f, " ({elapsed:.2?})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs `solution` against its real input and
+/// compares the result with the confirmed answer.
+pub fn verify(
+    solution: &'static Solution,
+    answers: &Answers,
+) -> Verification {
+    let path =
+        default_input_path(solution.day, solution.part);
+    let input = std::fs::read_to_string(&path);
+    verify_input(
+        solution,
+        path,
+        input,
+        answers.get(solution.day, solution.part),
+    )
+}
+
+/// Inputs aren't checked in, so an absent or
+/// empty placeholder counts as missing rather
+/// than as a puzzle the solution can't parse.
+/// Any other problem reading it is an error.
+fn verify_input(
+    solution: &'static Solution,
+    path: PathBuf,
+    input: io::Result<String>,
+    expected: Option<&str>,
+) -> Verification {
+    let outcome = match input {
+        Ok(input) if !input.trim().is_empty() => {
+            return verify_with(solution, &input, expected)
+        }
+        _ if !solution.is_implemented() => Outcome::Todo,
+        Ok(_) => Outcome::MissingInput { path },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            Outcome::MissingInput { path }
+        }
+        Err(e) => Outcome::Error(format!(
+            "reading `{}`: {e}",
+            path.display()
+        )),
+    };
+    Verification {
+        solution,
+        outcome,
+        elapsed: None,
+    }
+}
+
+pub fn verify_with(
+    solution: &'static Solution,
+    input: &str,
+    expected: Option<&str>,
+) -> Verification {
+    let Some(process) = solution.process else {
+        return Verification {
+            solution,
+            outcome: Outcome::Todo,
+            elapsed: None,
+        };
+    };
+
+    let start = Instant::now();
+    let result = process(input);
+    let elapsed = Some(start.elapsed());
+
+    let outcome = match (result, expected) {
+        (Err(e), _) => Outcome::Error(e.to_string()),
+        (Ok(actual), None) => {
+            Outcome::MissingAnswer { actual }
+        }
+        (Ok(actual), Some(expected))
+            if actual == expected =>
+        {
+            Outcome::Pass
+        }
+        (Ok(actual), Some(expected)) => Outcome::Fail {
+            expected: expected
+                .parse()
+                .unwrap_or_else(|e| match e {}),
+            actual,
+        },
+    };
+
+    Verification {
+        solution,
+        outcome,
+        elapsed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::find;
+
+    const INPUT: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn test_parse_answers() -> miette::Result<()> {
+        let answers = Answers::parse(
+            r#"
+["day-01"]
+part1 = "11"
+
+["day-07"]
+part2 = "11387"
+"#,
+        )?;
+        assert_eq!(
+            Some("11"),
+            answers.get("day-01", Part::Part1)
+        );
+        assert_eq!(
+            None,
+            answers.get("day-01", Part::Part2)
+        );
+        assert_eq!(
+            Some("11387"),
+            answers.get("day-07", Part::Part2)
+        );
+        assert_eq!(
+            None,
+            answers.get("day-02", Part::Part1)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_in_answers_parse() -> miette::Result<()>
+    {
+        Answers::load(&Answers::default_path())?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with() {
+        let solution = find("day-01", Part::Part1).unwrap();
+        assert_eq!(
+            Outcome::Pass,
+            verify_with(solution, INPUT, Some("11"))
+                .outcome
+        );
+        assert_eq!(
+            Outcome::Fail {
+                expected: Answer::Number(12),
+                actual: Answer::Number(11)
+            },
+            verify_with(solution, INPUT, Some("12"))
+                .outcome
+        );
+        assert_eq!(
+            Outcome::MissingAnswer {
+                actual: Answer::Number(11)
+            },
+            verify_with(solution, INPUT, None).outcome
+        );
+
+        static TODO: Solution = Solution {
+            day: "day-00",
+            part: Part::Part1,
+            process: None,
+            explain: None,
+        };
+        assert_eq!(
+            Outcome::Todo,
+            verify_with(&TODO, INPUT, Some("6")).outcome
+        );
+    }
+
+    #[test]
+    fn test_empty_input_is_missing() {
+        let solution = find("day-01", Part::Part1).unwrap();
+        let path = PathBuf::from("day-01/input1.txt");
+        let missing = || {
+            Err(io::Error::from(io::ErrorKind::NotFound))
+        };
+        for input in
+            [missing(), Ok("".into()), Ok(" \n".into())]
+        {
+            assert_eq!(
+                Outcome::MissingInput {
+                    path: path.clone()
+                },
+                verify_input(
+                    solution,
+                    path.clone(),
+                    input,
+                    Some("11")
+                )
+                .outcome
+            );
+        }
+        assert_eq!(
+            Outcome::Pass,
+            verify_input(
+                solution,
+                path.clone(),
+                Ok(INPUT.into()),
+                Some("11")
+            )
+            .outcome
+        );
+    }
+
+    #[test]
+    fn test_unreadable_input_is_an_error() {
+        let solution = find("day-01", Part::Part1).unwrap();
+        let outcome = verify_input(
+            solution,
+            PathBuf::from("day-01/input1.txt"),
+            Err(io::Error::from(
+                io::ErrorKind::PermissionDenied,
+            )),
+            Some("11"),
+        )
+        .outcome;
+        assert!(outcome.is_failure());
+        assert!(matches!(
+            outcome,
+            Outcome::Error(e) if e.starts_with("reading `day-01/input1.txt`")
+        ));
+    }
+}
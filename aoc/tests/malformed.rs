@@ -0,0 +1,137 @@
+//! Every registered `process` must turn bad input
+//! into an error instead of panicking.
+
+use std::{fs, panic};
+
+use aoc::{
+    input::workspace_root,
+    registry::{Process, SOLUTIONS},
+};
+use aoc_common::Answer;
+
+/// Days whose input is any text, like day 3's
+/// corrupted memory, so nothing is malformed.
+const FREE_TEXT: &[&str] = &["day-03"];
+
+/// The day's example inputs, from the fuzzing
+/// seeds in `fuzz/seeds/day-NN`.
+fn examples(day: &str) -> Vec<String> {
+    let dir = workspace_root().join("fuzz/seeds").join(day);
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| {
+            panic!("reading {}: {e}", dir.display())
+        })
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect()
+}
+
+/// Inputs that are wrong for every day with a
+/// structured input.
+fn malformed(example: &str) -> Vec<String> {
+    let middle = example
+        .char_indices()
+        .nth(example.chars().count() / 2)
+        .map_or(0, |(i, _)| i);
+    vec![
+        String::new(),
+        " ".to_string(),
+        "\n".to_string(),
+        "\r\n".to_string(),
+        "\t\n".to_string(),
+        format!("\u{feff}{example}"),
+        format!(" {example}"),
+        format!(
+            "{}é{}",
+            &example[..middle],
+            &example[middle..]
+        ),
+        format!(
+            "{}\0{}",
+            &example[..middle],
+            &example[middle..]
+        ),
+    ]
+}
+
+/// Inputs that only differ from `example` in
+/// ways every day should ignore.
+fn equivalent(example: &str) -> Vec<String> {
+    vec![
+        format!("{example}\n"),
+        format!("{example} \n\n"),
+        example.replace('\n', "\r\n"),
+    ]
+}
+
+/// `process(input)`, or `None` if it panicked.
+fn run(
+    process: Process,
+    input: &str,
+) -> Option<miette::Result<Answer>> {
+    panic::catch_unwind(|| process(input)).ok()
+}
+
+#[test]
+fn test_process_never_panics() {
+    let mut failures = vec![];
+    for solution in SOLUTIONS {
+        let Some(process) = solution.process else {
+            continue;
+        };
+        let name =
+            format!("{} {}", solution.day, solution.part);
+        let examples = examples(solution.day);
+        assert!(
+            !examples.is_empty(),
+            "{name} has no examples"
+        );
+
+        for example in &examples {
+            let truncated = example
+                .char_indices()
+                .map(|(i, _)| example[..i].to_string());
+            for input in truncated {
+                if run(process, &input).is_none() {
+                    failures.push(format!(
+                        "{name} panicked on {input:?}"
+                    ));
+                }
+            }
+
+            for input in malformed(example) {
+                match run(process, &input) {
+                    None => failures.push(format!("{name} panicked on {input:?}")),
+                    Some(Ok(answer)) if !FREE_TEXT.contains(&solution.day) => {
+                        failures.push(format!("{name} accepted {input:?} as {answer}"))
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            let expected =
+                run(process, example).and_then(Result::ok);
+            for input in equivalent(example) {
+                match run(process, &input) {
+                    None => failures.push(format!(
+                        "{name} panicked on {input:?}"
+                    )),
+                    Some(result) => {
+                        if result.ok() != expected {
+                            failures.push(format!("{name} changed its answer for {input:?}"));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{}",
+        failures.join("\n")
+    );
+}
@@ -1,3 +1,4 @@
+use aoc_common::Answer;
 use {{crate_name}}::*;
 
 fn main() {
@@ -6,17 +7,17 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
+fn part1() -> Answer {
     part1::process(divan::black_box(include_str!(
         "../input1.txt",
     )))
-    .unwrap();
+    .unwrap()
 }
 
 #[divan::bench]
-fn part2() {
+fn part2() -> Answer {
     part2::process(divan::black_box(include_str!(
         "../input2.txt",
     )))
-    .unwrap();
-}
\ No newline at end of file
+    .unwrap()
+}
@@ -0,0 +1,19 @@
+use aoc_common::generate::Generator;
+use rand::Rng;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub size: usize,
+}
+
+impl Generator for Params {
+    /// `size` is whatever best measures how big
+    /// the input is.
+    fn sized(size: usize) -> Self {
+        Self { size }
+    }
+
+    fn generate_with(&self, _rng: &mut impl Rng) -> String {
+        todo!("generate an input");
+    }
+}
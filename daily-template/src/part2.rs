@@ -1,5 +1,7 @@
+use aoc_common::Answer;
+
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String> {
+pub fn process(_input: &str) -> miette::Result<Answer> {
     todo!("day 01 - part 2");
 }
 
@@ -7,11 +9,15 @@ pub fn process(_input: &str) -> miette::Result<String> {
 mod tests {
     use super::*;
 
+    /// Filled in by `just examples` from the
+    /// puzzle description.
     #[test]
     fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
+        let input =
+            include_str!("../fixtures/part2.example.txt");
+        let answer =
+            include_str!("../fixtures/part2.answer.txt");
+        assert_eq!(answer.trim(), process(input)?);
         Ok(())
     }
 }
@@ -0,0 +1,65 @@
+use std::fmt::Write;
+
+use aoc_common::generate::Generator;
+use rand::{seq::SliceRandom, Rng};
+
+/// Pairs of location ids, one pair per line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub lines: usize,
+    /// How often a right id is copied from the
+    /// left list, so similarity isn't always 0.
+    pub repeats: f64,
+}
+
+impl Generator for Params {
+    /// `lines` pairs of five digit ids, at least
+    /// one.
+    fn sized(lines: usize) -> Self {
+        Self {
+            lines: lines.max(1),
+            repeats: 0.2,
+        }
+    }
+
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let left: Vec<i32> = (0..self.lines)
+            .map(|_| rng.gen_range(10_000..100_000))
+            .collect();
+
+        let mut out = String::new();
+        for l in &left {
+            let r = match left.choose(rng) {
+                Some(copy)
+                    if rng.gen_bool(self.repeats) =>
+                {
+                    *copy
+                }
+                _ => rng.gen_range(10_000..100_000),
+            };
+            let _ = writeln!(out, "{l}   {r}");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lists::LocationLists;
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        for seed in 0..5 {
+            let lists = LocationLists::parse(
+                &Params::generate(seed, 50),
+            )?;
+            assert_eq!(50, lists.left.len());
+        }
+        assert_eq!(
+            1,
+            Params::generate(0, 0).lines().count()
+        );
+        Ok(())
+    }
+}
@@ -1,2 +1,4 @@
+pub mod generate;
+pub mod lists;
 pub mod part1;
 pub mod part2;
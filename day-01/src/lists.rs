@@ -0,0 +1,246 @@
+use std::{collections::HashMap, io::BufRead};
+
+use aoc_common::error::{parse_all, IResult, ParseError};
+use miette::Diagnostic;
+use nom::{
+    character::complete::{self, line_ending, space1},
+    multi::separated_list1,
+    sequence::separated_pair,
+    Parser,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ReadError {
+    #[error("failed to read the location lists")]
+    #[diagnostic(code(day_01::io))]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// The two historians' lists of location ids,
+/// in the order they were written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocationLists {
+    pub left: Vec<i32>,
+    pub right: Vec<i32>,
+}
+
+fn pair(input: &str) -> IResult<(i32, i32)> {
+    separated_pair(complete::i32, space1, complete::i32)(
+        input,
+    )
+}
+
+impl LocationLists {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        parse_all(
+            input,
+            separated_list1(line_ending, pair).map(
+                |pairs| {
+                    let (left, right) =
+                        pairs.into_iter().unzip();
+                    Self { left, right }
+                },
+            ),
+        )
+    }
+
+    /// Reads one pair per line without holding
+    /// the whole input. Blank lines are skipped,
+    /// and errors point into the offending line.
+    pub fn from_reader(
+        mut reader: impl BufRead,
+    ) -> Result<Self, ReadError> {
+        let mut lists = Self::default();
+        let mut line = String::new();
+        let mut number = 0;
+        while reader.read_line(&mut line)? > 0 {
+            number += 1;
+            if !line.trim().is_empty() {
+                let (l, r) = parse_all(&line, pair)
+                    .map_err(|error| {
+                        error.with_name(&format!(
+                            "line {number}"
+                        ))
+                    })?;
+                lists.left.push(l);
+                lists.right.push(r);
+            }
+            line.clear();
+        }
+        Ok(lists)
+    }
+
+    /// The total gap between the smallest left
+    /// and right ids, then the next smallest, and
+    /// so on.
+    pub fn distance(&self) -> u64 {
+        let mut left = self.left.clone();
+        let mut right = self.right.clone();
+        left.sort_unstable();
+        right.sort_unstable();
+
+        std::iter::zip(left, right)
+            .map(|(l, r)| u64::from(l.abs_diff(r)))
+            .sum()
+    }
+
+    /// Each left id times how often it appears in
+    /// the right list, summed. Wider than the
+    /// ids, since enough repeats overflow an
+    /// `i64`.
+    pub fn similarity(&self) -> i128 {
+        let mut counts: HashMap<i32, i128> = HashMap::new();
+        for r in &self.right {
+            *counts.entry(*r).or_default() += 1;
+        }
+
+        self.left
+            .iter()
+            .map(|l| {
+                i128::from(*l)
+                    * counts
+                        .get(l)
+                        .copied()
+                        .unwrap_or_default()
+            })
+            .sum()
+    }
+
+    /// `similarity` by scanning the whole right
+    /// list for every left id. Quadratic, kept to
+    /// check `similarity` against.
+    pub fn similarity_naive(&self) -> i128 {
+        self.left
+            .iter()
+            .map(|l| {
+                let count = self
+                    .right
+                    .iter()
+                    .filter(|r| *r == l)
+                    .count();
+                i128::from(*l) * count as i128
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use aoc_common::property;
+    use proptest::{
+        collection::vec, prelude::*,
+        test_runner::TestCaseError,
+    };
+
+    const INPUT: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    #[test]
+    fn test_lists() -> miette::Result<()> {
+        let lists = LocationLists::parse(INPUT)?;
+        assert_eq!(vec![3, 4, 2, 1, 3, 3], lists.left);
+        assert_eq!(11, lists.distance());
+        assert_eq!(31, lists.similarity());
+
+        let extremes = LocationLists {
+            left: vec![i32::MIN, i32::MAX],
+            right: vec![i32::MAX, i32::MAX],
+        };
+        assert_eq!(
+            u64::from(u32::MAX),
+            extremes.distance()
+        );
+        assert_eq!(
+            2 * i128::from(i32::MAX),
+            extremes.similarity()
+        );
+
+        let repeats = LocationLists {
+            left: vec![i32::MAX; 100_000],
+            right: vec![i32::MAX; 100_000],
+        };
+        assert_eq!(
+            i128::from(i32::MAX) * 100_000 * 100_000,
+            repeats.similarity()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_reader() -> miette::Result<()> {
+        let lists = LocationLists::from_reader(
+            Cursor::new("3   4\r\n\n4   3"),
+        )?;
+        assert_eq!(
+            LocationLists {
+                left: vec![3, 4],
+                right: vec![4, 3],
+            },
+            lists
+        );
+        assert_eq!(
+            LocationLists::parse(INPUT)?,
+            LocationLists::from_reader(INPUT.as_bytes())?
+        );
+
+        let Err(ReadError::Parse(error)) =
+            LocationLists::from_reader(Cursor::new(
+                "3   4\n3\n",
+            ))
+        else {
+            panic!("a short line should fail to parse");
+        };
+        assert_eq!(1, error.offset());
+        assert_eq!(
+            "failed to parse line 2",
+            error.to_string()
+        );
+        Ok(())
+    }
+
+    /// Lists of small ids, so the right list has
+    /// plenty of repeats.
+    fn lists() -> impl Strategy<Value = String> {
+        vec((0..20i32, 0..20i32), 1..50).prop_map(|pairs| {
+            pairs
+                .iter()
+                .map(|(l, r)| format!("{l}   {r}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+
+    #[test]
+    fn test_similarity_matches_naive() -> miette::Result<()>
+    {
+        property::check(
+            env!("CARGO_MANIFEST_DIR"),
+            "similarity",
+            lists(),
+            |input| {
+                let lists = LocationLists::parse(input)
+                    .map_err(|e| {
+                        TestCaseError::fail(e.to_string())
+                    })?;
+                prop_assert_eq!(
+                    lists.similarity_naive(),
+                    lists.similarity()
+                );
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+}
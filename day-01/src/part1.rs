@@ -1,25 +1,12 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let mut left = vec![];
-    let mut right = vec![];
+use aoc_common::Answer;
 
-    for line in input.lines() {
-        let mut items = line.split_whitespace();
-        left.push(
-            items.next().unwrap().parse::<i32>().unwrap(),
-        );
-        right.push(
-            items.next().unwrap().parse::<i32>().unwrap(),
-        );
-    }
+use crate::lists::LocationLists;
 
-    left.sort();
-    right.sort();
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let lists = LocationLists::parse(input)?;
 
-    let result: i32 = std::iter::zip(left, right)
-        .map(|(l, r)| (l - r).abs())
-        .sum();
-    Ok(result.to_string())
+    Ok(lists.distance().into())
 }
 
 #[cfg(test)]
@@ -38,4 +25,13 @@ mod tests {
         assert_eq!("11", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_malformed_line() {
+        let error = process("3   4\n4   x\n").unwrap_err();
+        let error = error
+            .downcast_ref::<aoc_common::error::ParseError>()
+            .unwrap();
+        assert_eq!(6, error.offset());
+    }
 }
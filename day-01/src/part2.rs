@@ -1,30 +1,12 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let mut left = vec![];
-    let mut right = vec![];
-
-    for line in input.lines() {
-        let mut items = line.split_whitespace();
+use aoc_common::Answer;
 
-        left.push(
-            items.next().unwrap().parse::<i32>().unwrap(),
-        );
-        right.push(
-            items.next().unwrap().parse::<i32>().unwrap(),
-        );
-    }
+use crate::lists::LocationLists;
 
-    let result: i32 = left
-        .iter()
-        .map(|l| {
-            let occurences =
-                right.iter().filter(|&r| r == l).count()
-                    as i32;
-            l * occurences
-        })
-        .sum();
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let lists = LocationLists::parse(input)?;
 
-    Ok(result.to_string())
+    Ok(lists.similarity().into())
 }
 
 #[cfg(test)]
@@ -0,0 +1,73 @@
+use std::fmt::Write;
+
+use aoc_common::generate::Generator;
+use itertools::Itertools;
+use rand::Rng;
+
+/// Reports of levels, one per line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub reports: usize,
+    /// How many levels each report has.
+    pub levels: std::ops::RangeInclusive<usize>,
+    /// How often a level is replaced with a
+    /// random one, which usually makes the report
+    /// unsafe.
+    pub noise: f64,
+}
+
+impl Generator for Params {
+    /// `reports` reports like the real input's,
+    /// at least one.
+    fn sized(reports: usize) -> Self {
+        Self {
+            reports: reports.max(1),
+            levels: 5..=8,
+            noise: 0.1,
+        }
+    }
+
+    /// Each report starts out safe, going one way
+    /// in steps of 1 to 3, before the noise.
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let mut out = String::new();
+        for _ in 0..self.reports {
+            let len = rng.gen_range(self.levels.clone());
+            let direction =
+                if rng.gen_bool(0.5) { 1 } else { -1 };
+            let mut level: i32 = rng.gen_range(30..70);
+            let mut report = (0..len).map(|_| {
+                let current = if rng.gen_bool(self.noise) {
+                    rng.gen_range(1..100)
+                } else {
+                    level
+                };
+                level += direction * rng.gen_range(1..=3);
+                current
+            });
+            let _ = writeln!(out, "{}", report.join(" "));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safety::{parse, Tolerance};
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let reports = parse(&Params::generate(1, 200))?;
+        assert_eq!(200, reports.len());
+
+        // the noise leaves a mix of both
+        let strict = Tolerance::strict();
+        let safe = reports
+            .iter()
+            .filter(|r| strict.is_safe(r))
+            .count();
+        assert!(0 < safe && safe < 200);
+        Ok(())
+    }
+}
@@ -1,117 +1,32 @@
-use itertools::Itertools;
+use aoc_common::Answer;
 
-use miette::miette;
-use nom::{
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    IResult,
-};
-use tracing::instrument;
-
-type Report = Vec<i32>;
-
-enum Direction {
-    Increasing,
-    Decreasing,
-}
+use crate::safety::{explain as render, parse, Tolerance};
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, reports) = parse(input)
-        .map_err(|e| miette!("parse failed: {}", e))?;
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let reports = parse(input)?;
+    let tolerance = Tolerance::dampened();
 
     let result = reports
         .iter()
-        .filter(|report| {
-            if check_safety(report).is_err() {
-                for i in 0..report.len() {
-                    let mut new_report = (*report).clone();
-                    new_report.remove(i);
-                    if check_safety(&new_report).is_ok() {
-                        return true;
-                    } else {
-                        continue;
-                    }
-                }
-                false
-            } else {
-                true
-            }
-        })
+        .filter(|report| tolerance.is_safe(report))
         .count();
-    Ok(result.to_string())
-}
-
-#[instrument(ret)]
-fn check_safety(report: &Report) -> Result<(), String> {
-    let mut direction: Option<Direction> = None;
-    for (a, b) in report.iter().tuple_windows() {
-        let diff = a - b;
-        match diff.signum() {
-            // Negative case
-            -1 => match direction {
-                Some(Direction::Increasing) => {
-                    return Err(format!(
-                        "Switched to increasing: {}, {}",
-                        a, b
-                    ))
-                }
-                _ => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!(
-                            "{}, {} diff was {}",
-                            a,
-                            b,
-                            diff.abs()
-                        ));
-                    } else {
-                        direction =
-                            Some(Direction::Decreasing);
-                        continue;
-                    };
-                }
-            },
-            // Positive case
-            1 => match direction {
-                Some(Direction::Decreasing) => {
-                    return Err(format!(
-                        "Switched to decreasing: {}, {}",
-                        a, b
-                    ))
-                }
-                _ => {
-                    if !(1..=3).contains(&diff.abs()) {
-                        return Err(format!(
-                            "{}, {} diff was {}",
-                            a,
-                            b,
-                            diff.abs()
-                        ));
-                    } else {
-                        direction =
-                            Some(Direction::Increasing);
-                        continue;
-                    }
-                }
-            },
-            // No difference
-            0 => {
-                return Err(format!(
-                    "{} {} diff was 0",
-                    a, b
-                ))
-            }
-            _ => panic!("Should be -1, 0, or 1."),
-        }
-    }
-    Ok(())
+    Ok(result.into())
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Report>> {
-    separated_list1(
-        line_ending,
-        separated_list1(space1, complete::i32),
-    )(input)
+/// Lists the unsafe reports, why each one
+/// failed and which removal fixes it. Shows the
+/// first `limit` reports.
+pub fn explain(
+    input: &str,
+    limit: Option<usize>,
+) -> miette::Result<String> {
+    let reports = parse(input)?;
+    Ok(render(
+        &reports,
+        &Tolerance::dampened(),
+        limit,
+    ))
 }
 
 #[cfg(test)]
@@ -0,0 +1,509 @@
+use std::{fmt, fmt::Write, ops::RangeInclusive};
+
+use aoc_common::error::{parse_all, IResult, ParseError};
+use itertools::Itertools;
+use nom::{
+    character::complete::{self, line_ending, space1},
+    multi::separated_list1,
+};
+
+pub type Report = Vec<i32>;
+
+/// Why a report is unsafe. `index` is the level
+/// that broke the rule, compared with the one
+/// before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafetyViolation {
+    DirectionSwitch { index: usize },
+    GapTooLarge { index: usize, gap: u64 },
+    GapTooSmall { index: usize, gap: u64 },
+    ZeroGap { index: usize },
+}
+
+impl SafetyViolation {
+    pub fn index(&self) -> usize {
+        match self {
+            SafetyViolation::DirectionSwitch { index }
+            | SafetyViolation::GapTooLarge {
+                index, ..
+            }
+            | SafetyViolation::GapTooSmall {
+                index, ..
+            }
+            | SafetyViolation::ZeroGap { index } => *index,
+        }
+    }
+}
+
+impl fmt::Display for SafetyViolation {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            SafetyViolation::DirectionSwitch { index } => {
+                write!(
+                    f,
+                    "direction switch at index {index}"
+                )
+            }
+            SafetyViolation::GapTooLarge { index, gap } => {
+                write!(
+                    f,
+                    "gap of {gap} at index {index} is too large"
+                )
+            }
+            SafetyViolation::GapTooSmall { index, gap } => {
+                write!(
+                    f,
+                    "gap of {gap} at index {index} is too small"
+                )
+            }
+            SafetyViolation::ZeroGap { index } => {
+                write!(f, "zero gap at index {index}")
+            }
+        }
+    }
+}
+
+/// What counts as safe: every gap between
+/// adjacent levels is in `gaps`, all in the same
+/// direction, after removing at most `removals`
+/// levels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tolerance {
+    pub gaps: RangeInclusive<u64>,
+    pub removals: usize,
+}
+
+impl Tolerance {
+    /// Part 1: gaps of 1 to 3, nothing removed.
+    pub fn strict() -> Self {
+        Self {
+            gaps: 1..=3,
+            removals: 0,
+        }
+    }
+
+    /// Part 2: the Problem Dampener removes one
+    /// level.
+    pub fn dampened() -> Self {
+        Self {
+            removals: 1,
+            ..Self::strict()
+        }
+    }
+
+    /// The first rule `report` breaks as it is,
+    /// ignoring removals.
+    pub fn check(
+        &self,
+        report: &[i32],
+    ) -> Result<(), SafetyViolation> {
+        let mut direction = None;
+        for (i, (a, b)) in
+            report.iter().tuple_windows().enumerate()
+        {
+            let index = i + 1;
+            let diff = i64::from(*b) - i64::from(*a);
+            if diff != 0
+                && *direction.get_or_insert(diff.signum())
+                    != diff.signum()
+            {
+                return Err(
+                    SafetyViolation::DirectionSwitch {
+                        index,
+                    },
+                );
+            }
+
+            let gap = diff.unsigned_abs();
+            if !self.gaps.contains(&gap) {
+                return Err(if gap == 0 {
+                    SafetyViolation::ZeroGap { index }
+                } else if gap < *self.gaps.start() {
+                    SafetyViolation::GapTooSmall {
+                        index,
+                        gap,
+                    }
+                } else {
+                    SafetyViolation::GapTooLarge {
+                        index,
+                        gap,
+                    }
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The fewest levels to remove to make
+    /// `report` safe, or `None` when that takes
+    /// more than `removals`. Empty when it's
+    /// already safe.
+    pub fn fix(
+        &self,
+        report: &[i32],
+    ) -> Option<Vec<usize>> {
+        [1, -1]
+            .into_iter()
+            .filter_map(|direction| {
+                self.fix_towards(report, direction)
+            })
+            .min_by_key(Vec::len)
+    }
+
+    pub fn is_safe(&self, report: &[i32]) -> bool {
+        self.fix(report).is_some()
+    }
+
+    /// Finds the longest run of levels to keep
+    /// going in `direction`. With at most `k`
+    /// removals a kept level's predecessor is one
+    /// of the `k + 1` levels before it, so this
+    /// is linear in the report for a fixed
+    /// `k`.
+    fn fix_towards(
+        &self,
+        report: &[i32],
+        direction: i64,
+    ) -> Option<Vec<usize>> {
+        let k = self.removals;
+        let fits = |a: i32, b: i32| {
+            let diff =
+                (i64::from(b) - i64::from(a)) * direction;
+            diff >= 0 && self.gaps.contains(&(diff as u64))
+        };
+
+        // (levels removed so far, previous kept
+        // level) for keeping each level
+        let mut best: Vec<Option<(usize, Option<usize>)>> =
+            Vec::with_capacity(report.len());
+        for j in 0..report.len() {
+            let mut cell = (j <= k).then_some((j, None));
+            for i in j.saturating_sub(k + 1)..j {
+                let Some((removed, _)) = best[i] else {
+                    continue;
+                };
+                let removed = removed + (j - i - 1);
+                if removed <= k
+                    && fits(report[i], report[j])
+                    && cell.is_none_or(|(r, _)| removed < r)
+                {
+                    cell = Some((removed, Some(i)));
+                }
+            }
+            best.push(cell);
+        }
+
+        let n = report.len();
+        let (last, _) = best
+            .iter()
+            .enumerate()
+            .filter_map(|(j, cell)| {
+                cell.map(|(removed, _)| {
+                    (j, removed + (n - 1 - j))
+                })
+            })
+            .filter(|(_, removed)| *removed <= k)
+            .min_by_key(|(_, removed)| *removed)
+            .or((n == 0).then_some((0, 0)))?;
+
+        let mut kept = vec![false; n];
+        let mut level = (n > 0).then_some(last);
+        while let Some(j) = level {
+            kept[j] = true;
+            level =
+                best[j].and_then(|(_, previous)| previous);
+        }
+        Some(kept.iter().positions(|keep| !keep).collect())
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+/// Lists the first `limit` unsafe reports, or
+/// all of them, with why each one failed and the
+/// removals that fix it, followed by a tally.
+pub fn explain(
+    reports: &[Report],
+    tolerance: &Tolerance,
+    limit: Option<usize>,
+) -> String {
+    let mut out = String::new();
+    let (mut safe, mut fixed, mut unsafe_) = (0, 0, 0);
+    let mut listed = 0;
+    for report in reports {
+        let Err(violation) = tolerance.check(report) else {
+            safe += 1;
+            continue;
+        };
+        let fix = tolerance.fix(report);
+        match fix {
+            Some(_) => fixed += 1,
+            None => unsafe_ += 1,
+        }
+
+        if listed == limit.unwrap_or(usize::MAX) {
+            continue;
+        }
+        listed += 1;
+        let _ = write!(
+            out,
+            "{}: {violation}",
+            report.iter().join(" ")
+        );
+        let _ = match fix {
+            Some(removed) => writeln!(
+                out,
+                ", fixed by removing {}",
+                removed
+                    .iter()
+                    .map(|i| format!(
+                        "index {i} ({})",
+                        report[*i]
+                    ))
+                    .join(", ")
+            ),
+            None => writeln!(out),
+        };
+    }
+
+    let _ = write!(
+        out,
+        "{safe} safe, {fixed} fixed, {unsafe_} unsafe"
+    );
+    out
+}
+
+fn reports(input: &str) -> IResult<Vec<Report>> {
+    separated_list1(
+        line_ending,
+        separated_list1(space1, complete::i32),
+    )(input)
+}
+
+pub fn parse(
+    input: &str,
+) -> Result<Vec<Report>, ParseError> {
+    parse_all(input, reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::property;
+    use proptest::{
+        collection::vec, prelude::*,
+        test_runner::TestCaseError,
+    };
+
+    use super::*;
+
+    const INPUT: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+    #[test]
+    fn test_check() {
+        let strict = Tolerance::strict();
+        assert_eq!(Ok(()), strict.check(&[7, 6, 4, 2, 1]));
+        assert_eq!(
+            Err(SafetyViolation::GapTooLarge {
+                index: 2,
+                gap: 5
+            }),
+            strict.check(&[1, 2, 7, 8, 9])
+        );
+        assert_eq!(
+            Err(SafetyViolation::DirectionSwitch {
+                index: 2
+            }),
+            strict.check(&[1, 3, 2, 4, 5])
+        );
+        assert_eq!(
+            Err(SafetyViolation::ZeroGap { index: 3 }),
+            strict.check(&[8, 6, 4, 4, 1])
+        );
+
+        let wide = Tolerance {
+            gaps: 2..=5,
+            removals: 0,
+        };
+        assert_eq!(Ok(()), wide.check(&[1, 6, 9]));
+        assert_eq!(
+            Err(SafetyViolation::GapTooSmall {
+                index: 1,
+                gap: 1
+            }),
+            wide.check(&[1, 2, 7])
+        );
+    }
+
+    #[test]
+    fn test_fix() {
+        let dampened = Tolerance::dampened();
+        assert_eq!(
+            Some(vec![]),
+            dampened.fix(&[7, 6, 4, 2, 1])
+        );
+        assert_eq!(None, dampened.fix(&[1, 2, 7, 8, 9]));
+        assert_eq!(
+            Some(vec![2]),
+            dampened.fix(&[1, 3, 2, 4, 5])
+        );
+        assert_eq!(
+            Some(vec![3]),
+            dampened.fix(&[8, 6, 4, 4, 1])
+        );
+        // the first and last levels can go too
+        assert_eq!(
+            Some(vec![0]),
+            dampened.fix(&[9, 1, 2, 3])
+        );
+        assert_eq!(
+            Some(vec![3]),
+            dampened.fix(&[1, 2, 3, 9])
+        );
+        assert_eq!(Some(vec![]), dampened.fix(&[]));
+
+        let two = Tolerance {
+            removals: 2,
+            ..Tolerance::strict()
+        };
+        assert_eq!(
+            Some(vec![1, 2]),
+            two.fix(&[1, 9, 9, 2, 3])
+        );
+    }
+
+    /// The linear fix agrees with removing each
+    /// level in turn for every short report.
+    #[test]
+    fn test_fix_matches_brute_force() {
+        let dampened = Tolerance::dampened();
+        let strict = Tolerance::strict();
+        for report in
+            (0..5).map(|_| 1..=6).multi_cartesian_product()
+        {
+            let brute = strict.check(&report).is_ok()
+                || (0..report.len()).any(|i| {
+                    let mut removed = report.clone();
+                    removed.remove(i);
+                    strict.check(&removed).is_ok()
+                });
+            assert_eq!(
+                brute,
+                dampened.is_safe(&report),
+                "{report:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_explain() -> miette::Result<()> {
+        let reports = parse(INPUT)?;
+        assert_eq!(
+            "1 2 7 8 9: gap of 5 at index 2 is too large
+9 7 6 2 1: gap of 4 at index 3 is too large
+1 3 2 4 5: direction switch at index 2, fixed by removing index 2 (2)
+8 6 4 4 1: zero gap at index 3, fixed by removing index 3 (4)
+2 safe, 2 fixed, 2 unsafe",
+            explain(&reports, &Tolerance::dampened(), None)
+        );
+        assert_eq!(
+            2,
+            explain(
+                &reports,
+                &Tolerance::strict(),
+                Some(1)
+            )
+            .lines()
+            .count()
+        );
+        assert_eq!(
+            "gap of 1 at index 4 is too small",
+            SafetyViolation::GapTooSmall {
+                index: 4,
+                gap: 1
+            }
+            .to_string()
+        );
+        Ok(())
+    }
+
+    fn inputs() -> impl Strategy<Value = String> {
+        vec(vec(1..=9i32, 1..=8), 1..10).prop_map(
+            |reports| {
+                reports
+                    .iter()
+                    .map(|report| report.iter().join(" "))
+                    .join("\n")
+            },
+        )
+    }
+
+    /// Whether removing some `removals` levels
+    /// leaves a strictly safe report, by trying
+    /// every choice.
+    fn brute_force(
+        report: &[i32],
+        removals: usize,
+    ) -> bool {
+        let strict = Tolerance::strict();
+        (0..=removals.min(report.len())).any(|k| {
+            (0..report.len()).combinations(k).any(
+                |removed| {
+                    let kept: Vec<i32> = report
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| {
+                            !removed.contains(i)
+                        })
+                        .map(|(_, level)| *level)
+                        .collect();
+                    strict.check(&kept).is_ok()
+                },
+            )
+        })
+    }
+
+    #[test]
+    fn test_is_safe_matches_brute_force(
+    ) -> miette::Result<()> {
+        property::check(
+            env!("CARGO_MANIFEST_DIR"),
+            "is_safe",
+            inputs(),
+            |input| {
+                let reports =
+                    parse(input).map_err(|e| {
+                        TestCaseError::fail(e.to_string())
+                    })?;
+                for removals in 0..=2 {
+                    let tolerance = Tolerance {
+                        removals,
+                        ..Tolerance::strict()
+                    };
+                    for report in &reports {
+                        prop_assert_eq!(
+                            brute_force(report, removals),
+                            tolerance.is_safe(report),
+                            "{:?} removing {}",
+                            report,
+                            removals
+                        );
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+}
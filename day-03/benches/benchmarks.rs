@@ -1,3 +1,4 @@
+use aoc_common::{generate::Generator, Answer};
 use day_03::*;
 
 fn main() {
@@ -6,17 +7,31 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
+fn part1() -> Answer {
     part1::process(divan::black_box(include_str!(
         "../input1.txt",
     )))
-    .unwrap();
+    .unwrap()
 }
 
 #[divan::bench]
-fn part2() {
+fn part2() -> Answer {
     part2::process(divan::black_box(include_str!(
         "../input2.txt",
     )))
-    .unwrap();
-}
\ No newline at end of file
+    .unwrap()
+}
+
+#[divan::bench(args = [10, 100, 1000])]
+fn part1_generated(bencher: divan::Bencher, size: usize) {
+    bencher
+        .with_inputs(|| generate::Params::generate(0, size))
+        .bench_refs(|input| part1::process(input).unwrap());
+}
+
+#[divan::bench(args = [10, 100, 1000])]
+fn part2_generated(bencher: divan::Bencher, size: usize) {
+    bencher
+        .with_inputs(|| generate::Params::generate(0, size))
+        .bench_refs(|input| part2::process(input).unwrap());
+}
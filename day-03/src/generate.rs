@@ -0,0 +1,97 @@
+use aoc_common::generate::Generator;
+use rand::{seq::SliceRandom, Rng};
+
+/// Corrupted memory mixing instructions, junk
+/// and near misses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    /// How many fragments, valid or not, to
+    /// write.
+    pub fragments: usize,
+    /// How often a fragment is a near miss like
+    /// `mul(32,64]` rather than an instruction.
+    pub near_misses: f64,
+}
+
+impl Generator for Params {
+    fn sized(fragments: usize) -> Self {
+        Self {
+            fragments,
+            near_misses: 0.3,
+        }
+    }
+
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let mut out = String::new();
+        for _ in 0..self.fragments {
+            for _ in 0..rng.gen_range(0..4) {
+                out.push_str(JUNK.choose(rng).unwrap());
+            }
+            if rng.gen_bool(self.near_misses) {
+                out.push_str(
+                    NEAR_MISSES.choose(rng).unwrap(),
+                );
+                continue;
+            }
+            match rng.gen_range(0..10) {
+                0 => out.push_str("do()"),
+                1 => out.push_str("don't()"),
+                _ => out.push_str(&format!(
+                    "mul({},{})",
+                    rng.gen_range(0..1000),
+                    rng.gen_range(0..1000)
+                )),
+            }
+        }
+        out.push('\n');
+        out
+    }
+}
+
+const JUNK: &[&str] = &[
+    "x", "%", "&", "!", "@", "^", "(", ")", "[", "]", "{",
+    "}", "<", ">", "'", ",", " ", "+", "-", "*", "?",
+    "why", "what", "from", "select", "when", "how",
+];
+
+const NEAR_MISSES: &[&str] = &[
+    "mul[3,7]",
+    "mul(32,64]",
+    "mul ( 2 , 4 )",
+    "mul(4*",
+    "mul(6,9!",
+    "?(12,34)",
+    "do_not",
+    "don't",
+    "do(",
+];
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::interpreter::{candidates, scan, PART2};
+
+    #[test]
+    fn test_generate() {
+        // without near misses every fragment is
+        // one instruction
+        let clean = Params {
+            near_misses: 0.0,
+            ..Params::sized(200)
+        };
+        let input = clean
+            .generate_with(&mut StdRng::seed_from_u64(1));
+        assert_eq!(200, scan(PART2, &input).len());
+
+        let input = Params::generate(1, 200);
+        let candidates = candidates(PART2, &input);
+        assert!(candidates
+            .iter()
+            .any(|c| c.outcome.is_ok()));
+        assert!(candidates
+            .iter()
+            .any(|c| c.outcome.is_err()));
+    }
+}
@@ -0,0 +1,384 @@
+use aoc_common::error::{self, furthest, IResult};
+use nom::{
+    character::complete,
+    combinator::value,
+    sequence::{delimited, preceded, separated_pair},
+    Parser,
+};
+use nom_supreme::tag::complete::tag;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// Adds `a * b` to the total.
+    Mul(u32, u32),
+    /// Adds `a + b` to the total.
+    Add(u32, u32),
+    /// Negates the total.
+    Neg,
+    Do,
+    Dont,
+}
+
+/// What the program has done so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    pub enabled: bool,
+    pub total: i128,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            total: 0,
+        }
+    }
+}
+
+impl Instruction {
+    /// Arithmetic only happens while enabled;
+    /// toggles always apply.
+    pub fn execute(&self, state: &mut State) {
+        match self {
+            Instruction::Do => state.enabled = true,
+            Instruction::Dont => state.enabled = false,
+            _ if !state.enabled => {}
+            Instruction::Mul(a, b) => {
+                state.total +=
+                    i128::from(*a) * i128::from(*b)
+            }
+            Instruction::Add(a, b) => {
+                state.total +=
+                    i128::from(*a) + i128::from(*b)
+            }
+            Instruction::Neg => state.total = -state.total,
+        }
+    }
+}
+
+/// Parses one kind of instruction. The scanner
+/// only tries `parse` where the input starts
+/// with `keyword`.
+#[derive(Clone, Copy)]
+pub struct InstructionParser {
+    pub keyword: &'static str,
+    pub parse:
+        for<'a> fn(&'a str) -> IResult<'a, Instruction>,
+}
+
+fn pair(input: &str) -> IResult<(u32, u32)> {
+    delimited(
+        tag("("),
+        separated_pair(
+            complete::u32,
+            tag(","),
+            complete::u32,
+        ),
+        tag(")"),
+    )(input)
+}
+
+pub const MUL: InstructionParser = InstructionParser {
+    keyword: "mul",
+    parse: |input| {
+        preceded(tag("mul"), pair)
+            .map(|(a, b)| Instruction::Mul(a, b))
+            .parse(input)
+    },
+};
+
+pub const ADD: InstructionParser = InstructionParser {
+    keyword: "add",
+    parse: |input| {
+        preceded(tag("add"), pair)
+            .map(|(a, b)| Instruction::Add(a, b))
+            .parse(input)
+    },
+};
+
+pub const NEG: InstructionParser = InstructionParser {
+    keyword: "neg",
+    parse: |input| {
+        value(Instruction::Neg, tag("neg()"))(input)
+    },
+};
+
+pub const DO: InstructionParser = InstructionParser {
+    keyword: "do",
+    parse: |input| {
+        value(Instruction::Do, tag("do()"))(input)
+    },
+};
+
+pub const DONT: InstructionParser = InstructionParser {
+    keyword: "don't",
+    parse: |input| {
+        value(Instruction::Dont, tag("don't()"))(input)
+    },
+};
+
+pub const PART1: &[InstructionParser] = &[MUL];
+pub const PART2: &[InstructionParser] = &[MUL, DO, DONT];
+
+/// An instruction the scanner found and the
+/// state after running it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub offset: usize,
+    pub instruction: Instruction,
+    pub state: State,
+}
+
+/// A place where an instruction keyword appears
+/// in the input, and whether it parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub offset: usize,
+    /// The whole instruction, or up to and
+    /// including where it went wrong.
+    pub len: usize,
+    /// Why it was rejected, when it was.
+    pub outcome: Result<Instruction, String>,
+}
+
+/// Tries the parsers at every keyword in
+/// `input`. Each position is tried once and a
+/// match skips past the instruction, so this is
+/// linear in the input.
+pub fn candidates(
+    parsers: &[InstructionParser],
+    input: &str,
+) -> Vec<Candidate> {
+    let mut candidates = vec![];
+    let mut offset = 0;
+    while let Some(found) = input[offset..].find(|c| {
+        parsers.iter().any(|p| p.keyword.starts_with(c))
+    }) {
+        offset += found;
+        let rest = &input[offset..];
+        let mut rejected: Option<(usize, String)> = None;
+        let mut accepted = None;
+        for parser in parsers
+            .iter()
+            .filter(|p| rest.starts_with(p.keyword))
+        {
+            match (parser.parse)(rest) {
+                Ok((after, instruction)) => {
+                    accepted = Some((after, instruction));
+                    break;
+                }
+                Err(nom::Err::Error(tree))
+                | Err(nom::Err::Failure(tree)) => {
+                    let (location, label, _) =
+                        furthest(&tree);
+                    let at = error::offset(rest, location);
+                    // where it went wrong, or else the
+                    // whole keyword
+                    let len = (at + 1)
+                        .max(parser.keyword.len())
+                        .min(rest.len());
+                    if rejected
+                        .as_ref()
+                        .is_none_or(|(l, _)| len > *l)
+                    {
+                        rejected = Some((len, label));
+                    }
+                }
+                Err(nom::Err::Incomplete(_)) => {}
+            }
+        }
+
+        match (accepted, rejected) {
+            (Some((after, instruction)), _) => {
+                let len = rest.len() - after.len();
+                candidates.push(Candidate {
+                    offset,
+                    len,
+                    outcome: Ok(instruction),
+                });
+                offset += len;
+            }
+            (None, rejected) => {
+                if let Some((len, reason)) = rejected {
+                    candidates.push(Candidate {
+                        offset,
+                        len,
+                        outcome: Err(reason),
+                    });
+                }
+                // keywords are ascii, so this stays on
+                // a char boundary
+                offset += 1;
+            }
+        }
+    }
+    candidates
+}
+
+/// Every valid instruction in `input` with its
+/// byte offset, skipping anything else.
+pub fn scan(
+    parsers: &[InstructionParser],
+    input: &str,
+) -> Vec<(usize, Instruction)> {
+    candidates(parsers, input)
+        .into_iter()
+        .filter_map(|candidate| {
+            candidate.outcome.ok().map(|instruction| {
+                (candidate.offset, instruction)
+            })
+        })
+        .collect()
+}
+
+pub struct Interpreter<'p> {
+    parsers: &'p [InstructionParser],
+    state: State,
+    trace: Vec<Step>,
+}
+
+impl<'p> Interpreter<'p> {
+    pub fn new(parsers: &'p [InstructionParser]) -> Self {
+        Self {
+            parsers,
+            state: State::default(),
+            trace: vec![],
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn trace(&self) -> &[Step] {
+        &self.trace
+    }
+
+    pub fn step(
+        &mut self,
+        offset: usize,
+        instruction: Instruction,
+    ) {
+        instruction.execute(&mut self.state);
+        self.trace.push(Step {
+            offset,
+            instruction,
+            state: self.state,
+        });
+    }
+
+    /// Scans `input` and runs every instruction
+    /// in it, carrying on from the current state.
+    pub fn run(&mut self, input: &str) -> State {
+        for (offset, instruction) in
+            scan(self.parsers, input)
+        {
+            self.step(offset, instruction);
+        }
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        assert_eq!(
+            vec![
+                (1, Instruction::Mul(2, 4)),
+                (20, Instruction::Dont),
+                (28, Instruction::Mul(5, 5)),
+                (48, Instruction::Mul(11, 8)),
+                (59, Instruction::Do),
+                (64, Instruction::Mul(8, 5)),
+            ],
+            scan(PART2, input)
+        );
+        assert_eq!(4, scan(PART1, input).len());
+        // a failed candidate doesn't hide one
+        // starting inside it
+        assert_eq!(
+            vec![(3, Instruction::Mul(1, 2))],
+            scan(PART1, "mulmul(1,2)")
+        );
+        assert_eq!(
+            vec![(3, Instruction::Mul(1, 2))],
+            scan(PART1, "é!mul(1,2)")
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut interpreter = Interpreter::new(PART2);
+        let state =
+            interpreter.run("mul(2,3)don't()mul(4,5)do()");
+        assert_eq!(
+            State {
+                enabled: true,
+                total: 6
+            },
+            state
+        );
+        assert_eq!(
+            vec![6, 6, 6, 6],
+            interpreter
+                .trace()
+                .iter()
+                .map(|step| step.state.total)
+                .collect::<Vec<_>>()
+        );
+        assert!(!interpreter.trace()[2].state.enabled);
+    }
+
+    #[test]
+    fn test_extra_ops() {
+        let mut interpreter =
+            Interpreter::new(&[MUL, ADD, NEG, DO, DONT]);
+        let state = interpreter.run(
+            "add(1,2)neg()mul(2,2)don't()neg()do()add(0,1)",
+        );
+        assert_eq!(2, state.total);
+        assert_eq!(
+            i128::from(u32::MAX).pow(2) * 2,
+            Interpreter::new(PART1)
+                .run("mul(4294967295,4294967295)mul(4294967295,4294967295)")
+                .total
+        );
+        // the part 2 set doesn't know `add`
+        assert_eq!(
+            4,
+            Interpreter::new(PART2)
+                .run("add(1,2)mul(2,2)")
+                .total
+        );
+    }
+
+    #[test]
+    fn test_candidates() {
+        let input = "mul[3,7]!do_not_mul(5,5)+mul(32,64]";
+        let found: Vec<_> = candidates(PART2, input)
+            .into_iter()
+            .map(|c| (c.offset, c.len, c.outcome))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, 4, Err("expected \"(\"".to_string())),
+                (
+                    9,
+                    2,
+                    Err("expected \"do()\"".to_string())
+                ),
+                (16, 8, Ok(Instruction::Mul(5, 5))),
+                (
+                    25,
+                    10,
+                    Err("expected \")\"".to_string())
+                ),
+            ],
+            found
+        );
+    }
+}
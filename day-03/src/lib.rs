@@ -1,2 +1,5 @@
+pub mod generate;
+pub mod interpreter;
 pub mod part1;
 pub mod part2;
+pub mod report;
@@ -1,55 +1,25 @@
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, anychar},
-    multi::{many1, many_till},
-    sequence::{delimited, separated_pair},
-    IResult, Parser,
+use aoc_common::Answer;
+
+use crate::{
+    interpreter::{Interpreter, PART1},
+    report::ScanReport,
 };
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, instructions) = parse(input).unwrap();
-
-    let result: u32 = instructions
-        .iter()
-        .map(|i| match i {
-            Instruction::Mul(x, y) => x * y,
-        })
-        .sum();
-
-    Ok(result.to_string())
-}
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let result = Interpreter::new(PART1).run(input).total;
 
-#[derive(Debug, PartialEq, Eq)]
-enum Instruction {
-    Mul(u32, u32),
+    Ok(result.into())
 }
 
-fn parse_instruction(
+/// Annotates the input with every instruction
+/// the scanner accepted and every near miss it
+/// rejected, up to `limit` of them.
+pub fn explain(
     input: &str,
-) -> IResult<&str, Instruction> {
-    let (input, _) = tag("mul")(input)?;
-
-    let (input, pair) = delimited(
-        tag("("),
-        separated_pair(
-            complete::u32,
-            tag(","),
-            complete::u32,
-        ),
-        tag(")"),
-    )(input)?;
-
-    Ok((input, Instruction::Mul(pair.0, pair.1)))
-}
-
-fn parse(input: &str) -> IResult<&str, Vec<Instruction>> {
-    let (input, result) = many1(
-        many_till(anychar, parse_instruction)
-            .map(|(_, ins)| ins),
-    )(input)?;
-
-    Ok((input, result))
+    limit: Option<usize>,
+) -> miette::Result<String> {
+    Ok(ScanReport::new(PART1, input, limit).render())
 }
 
 #[cfg(test)]
@@ -62,16 +32,4 @@ mod tests {
         assert_eq!("161", process(input)?);
         Ok(())
     }
-
-    #[test]
-    fn test_instruction() -> miette::Result<()> {
-        let input = "mul(3,2)";
-        let (_, instruction) =
-            match parse_instruction(input) {
-                Ok(it) => it,
-                _ => ("abc", Instruction::Mul(0, 0)),
-            };
-        assert_eq!(Instruction::Mul(3, 2), instruction);
-        Ok(())
-    }
 }
@@ -0,0 +1,115 @@
+use miette::{
+    Diagnostic, GraphicalReportHandler, GraphicalTheme,
+    LabeledSpan, NamedSource,
+};
+use thiserror::Error;
+
+use crate::interpreter::{candidates, InstructionParser};
+
+/// Every instruction the scanner accepted and
+/// every candidate it rejected, as labels on the
+/// input.
+#[derive(Debug, Error, Diagnostic)]
+#[error("{accepted} instructions accepted, {rejected} candidates rejected")]
+#[diagnostic(code(day_03::scan))]
+pub struct ScanReport {
+    #[source_code]
+    src: NamedSource<String>,
+    #[label(collection)]
+    labels: Vec<LabeledSpan>,
+    pub accepted: usize,
+    pub rejected: usize,
+}
+
+impl ScanReport {
+    /// Labels the first `limit` candidates, or
+    /// all of them. The counts cover every
+    /// candidate either way.
+    pub fn new(
+        parsers: &[InstructionParser],
+        input: &str,
+        limit: Option<usize>,
+    ) -> Self {
+        let candidates = candidates(parsers, input);
+        let accepted = candidates
+            .iter()
+            .filter(|candidate| candidate.outcome.is_ok())
+            .count();
+        let labels = candidates
+            .iter()
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|candidate| {
+                let label = match &candidate.outcome {
+                    Ok(_) => "accepted".to_string(),
+                    Err(reason) => reason.clone(),
+                };
+                LabeledSpan::new_with_span(
+                    Some(label),
+                    (candidate.offset, candidate.len),
+                )
+            })
+            .collect();
+
+        Self {
+            src: NamedSource::new(
+                "input",
+                input.to_string(),
+            ),
+            labels,
+            accepted,
+            rejected: candidates.len() - accepted,
+        }
+    }
+
+    /// The annotated listing, without colors so
+    /// it reads the same anywhere.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = GraphicalReportHandler::new_themed(
+            GraphicalTheme::unicode_nocolor(),
+        )
+        .render_report(&mut out, self);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{PART1, PART2};
+
+    const INPUT: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_counts() {
+        let report = ScanReport::new(PART1, INPUT, None);
+        assert_eq!(
+            (4, 2),
+            (report.accepted, report.rejected)
+        );
+        assert_eq!(
+            "4 instructions accepted, 2 candidates rejected",
+            report.to_string()
+        );
+
+        let report = ScanReport::new(PART2, INPUT, Some(2));
+        assert_eq!(
+            (6, 2),
+            (report.accepted, report.rejected)
+        );
+        assert_eq!(2, report.labels.len());
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = ScanReport::new(
+            PART1,
+            "mul(1,2)mul[3,7]",
+            None,
+        )
+        .render();
+        assert!(rendered.contains("day_03::scan"));
+        assert!(rendered.contains("accepted"));
+        assert!(rendered.contains("expected \"(\""));
+    }
+}
@@ -0,0 +1,56 @@
+use aoc_common::generate::Generator;
+use rand::{seq::SliceRandom, Rng};
+
+/// A grid of letters to search for words in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub width: usize,
+    pub height: usize,
+    pub letters: Vec<char>,
+}
+
+impl Generator for Params {
+    /// A `size` by `size` grid of `XMAS`
+    /// letters, at least one cell.
+    fn sized(size: usize) -> Self {
+        Self {
+            width: size.max(1),
+            height: size.max(1),
+            letters: vec!['X', 'M', 'A', 'S'],
+        }
+    }
+
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let mut out = String::new();
+        for _ in 0..self.height {
+            for _ in 0..self.width {
+                out.push(
+                    *self
+                        .letters
+                        .choose(rng)
+                        .unwrap_or(&'.'),
+                );
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{Directions, WordSearch};
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let search =
+            WordSearch::parse(&Params::generate(1, 40))?;
+        assert_eq!(40, search.grid().width());
+        assert_eq!(40, search.grid().height());
+        assert!(!search
+            .find_word("XMAS", Directions::ALL)
+            .is_empty());
+        Ok(())
+    }
+}
@@ -1,55 +1,15 @@
-use std::collections::HashMap;
+use aoc_common::Answer;
 
-use glam::IVec2;
-
-const DIRECTIONS: [[IVec2; 2]; 4] = [
-    [IVec2::new(1, 1), IVec2::new(-1, -1)],
-    [IVec2::new(1, -1), IVec2::new(-1, 1)],
-    [IVec2::new(-1, 1), IVec2::new(1, -1)],
-    [IVec2::new(-1, -1), IVec2::new(1, 1)],
-];
+use crate::search::{Pattern, WordSearch};
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let positions = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().map(
-                move |(x, value)| {
-                    (IVec2::new(x as i32, y as i32), value)
-                },
-            )
-        })
-        .collect::<HashMap<IVec2, char>>();
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let search = WordSearch::parse(input)?;
 
-    let mas = ['M', 'S'];
-    let result: usize = positions
-        .iter()
-        .filter(|(_pos, value)| **value == 'A')
-        .map(|(position, _value)| {
-            let count = DIRECTIONS
-                .iter()
-                .map(|mas_pos| {
-                    mas_pos
-                        .iter()
-                        .map(|offset| {
-                            positions
-                                .get(&(position + offset))
-                        })
-                        .enumerate()
-                        .all(|(index, value)| {
-                            mas.get(index) == value
-                        })
-                })
-                .filter(|b| *b)
-                .count();
-            count
-        })
-        .filter(|v| *v > 1)
-        .count();
+    let result =
+        search.find_pattern(&Pattern::x_mas()).len();
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
 #[cfg(test)]
@@ -0,0 +1,313 @@
+use aoc_common::error::ParseError;
+use aoc_grid::Grid;
+use glam::IVec2;
+
+/// Which ways a word may run. Forwards is
+/// left-to-right and top-to-bottom, diagonals
+/// included; `reversed` adds the opposite of
+/// each allowed direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Directions {
+    pub straight: bool,
+    pub diagonal: bool,
+    pub reversed: bool,
+}
+
+impl Directions {
+    pub const ALL: Self = Self {
+        straight: true,
+        diagonal: true,
+        reversed: true,
+    };
+
+    /// The step between letters for each allowed
+    /// direction.
+    pub fn vectors(&self) -> Vec<IVec2> {
+        let mut forwards = vec![];
+        if self.straight {
+            forwards.extend([IVec2::X, IVec2::Y]);
+        }
+        if self.diagonal {
+            forwards.extend([
+                IVec2::new(1, 1),
+                IVec2::new(-1, 1),
+            ]);
+        }
+        let backwards: Vec<_> =
+            forwards.iter().map(|dir| -*dir).collect();
+        if self.reversed {
+            forwards.extend(backwards);
+        }
+        forwards
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordMatch {
+    /// Where the first letter is.
+    pub start: IVec2,
+    /// The step to each following letter.
+    pub direction: IVec2,
+}
+
+/// A rectangle of letters where `None` matches
+/// anything, found in any quarter turn.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    /// Each distinct rotation with how many
+    /// quarter turns clockwise it is.
+    orientations: Vec<(u8, Grid<Option<char>>)>,
+}
+
+impl Pattern {
+    pub fn new(cells: Grid<Option<char>>) -> Self {
+        let mut orientations: Vec<(u8, Grid<_>)> = vec![];
+        let mut rotated = cells;
+        for turns in 0..4 {
+            if !orientations
+                .iter()
+                .any(|(_, seen)| *seen == rotated)
+            {
+                orientations.push((turns, rotated.clone()));
+            }
+            rotated = rotated.rotate_cw();
+        }
+        Self { orientations }
+    }
+
+    /// Parses a pattern where `wildcard` matches
+    /// any letter.
+    pub fn parse(
+        input: &str,
+        wildcard: char,
+    ) -> Result<Self, ParseError> {
+        Grid::parse_with(input, |c| {
+            Some((c != wildcard).then_some(c))
+        })
+        .map(Self::new)
+    }
+
+    /// Two `MAS` crossing at the `A`, each read
+    /// either way.
+    pub fn x_mas() -> Self {
+        Self::parse("M.S\n.A.\nM.S", '.')
+            .expect("the X-MAS pattern is valid")
+    }
+
+    pub fn orientations(&self) -> usize {
+        self.orientations.len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternMatch {
+    /// Where the rotated pattern's top left
+    /// corner is.
+    pub top_left: IVec2,
+    /// Quarter turns clockwise from the pattern
+    /// as written.
+    pub turns: u8,
+}
+
+pub struct WordSearch {
+    grid: Grid<char>,
+}
+
+impl WordSearch {
+    pub fn new(grid: Grid<char>) -> Self {
+        Self { grid }
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Grid::parse(input).map(Self::new)
+    }
+
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
+
+    /// Every place `word` can be read in one of
+    /// `directions`. A single letter matches once
+    /// per cell, with no direction.
+    pub fn find_word(
+        &self,
+        word: &str,
+        directions: Directions,
+    ) -> Vec<WordMatch> {
+        let letters: Vec<char> = word.chars().collect();
+        let Some((first, rest)) = letters.split_first()
+        else {
+            return vec![];
+        };
+        let vectors = if rest.is_empty() {
+            vec![IVec2::ZERO]
+        } else {
+            directions.vectors()
+        };
+
+        self.grid
+            .find_all(|cell| cell == first)
+            .flat_map(|start| {
+                vectors
+                    .iter()
+                    .filter(move |direction| {
+                        rest.iter().zip(1..).all(
+                            |(letter, i)| {
+                                self.grid.get(
+                                    start + **direction * i,
+                                ) == Some(letter)
+                            },
+                        )
+                    })
+                    .map(move |direction| WordMatch {
+                        start,
+                        direction: *direction,
+                    })
+            })
+            .collect()
+    }
+
+    /// Every place and rotation where `pattern`
+    /// fits.
+    pub fn find_pattern(
+        &self,
+        pattern: &Pattern,
+    ) -> Vec<PatternMatch> {
+        let mut matches = vec![];
+        for (turns, cells) in &pattern.orientations {
+            let Some(width) = (self.grid.width() + 1)
+                .checked_sub(cells.width())
+            else {
+                continue;
+            };
+            let Some(height) = (self.grid.height() + 1)
+                .checked_sub(cells.height())
+            else {
+                continue;
+            };
+
+            for y in 0..height as i32 {
+                for x in 0..width as i32 {
+                    let top_left = IVec2::new(x, y);
+                    let fits = cells.iter().all(
+                        |(offset, cell)| {
+                            cell.is_none_or(|letter| {
+                                self.grid
+                                    .get(top_left + offset)
+                                    == Some(&letter)
+                            })
+                        },
+                    );
+                    if fits {
+                        matches.push(PatternMatch {
+                            top_left,
+                            turns: *turns,
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(8, Directions::ALL.vectors().len());
+        assert_eq!(
+            vec![
+                IVec2::X,
+                IVec2::Y,
+                IVec2::NEG_X,
+                IVec2::NEG_Y
+            ],
+            Directions {
+                straight: true,
+                diagonal: false,
+                reversed: true,
+            }
+            .vectors()
+        );
+        // forwards never runs upwards
+        assert!(Directions {
+            reversed: false,
+            ..Directions::ALL
+        }
+        .vectors()
+        .iter()
+        .all(|dir| dir.y >= 0));
+    }
+
+    #[test]
+    fn test_find_word() -> miette::Result<()> {
+        let search =
+            WordSearch::parse("XMAS\nMM..\nA.A.\nS..S")?;
+        let forwards = Directions {
+            straight: true,
+            diagonal: true,
+            reversed: false,
+        };
+        assert_eq!(
+            vec![
+                WordMatch {
+                    start: IVec2::ZERO,
+                    direction: IVec2::X
+                },
+                WordMatch {
+                    start: IVec2::ZERO,
+                    direction: IVec2::Y
+                },
+                WordMatch {
+                    start: IVec2::ZERO,
+                    direction: IVec2::new(1, 1)
+                },
+            ],
+            search.find_word("XMAS", forwards)
+        );
+        // backwards along the top, the left and
+        // the diagonal
+        let reversed =
+            search.find_word("SAMX", Directions::ALL);
+        assert_eq!(3, reversed.len());
+        assert_eq!(
+            WordMatch {
+                start: IVec2::new(3, 0),
+                direction: IVec2::NEG_X
+            },
+            reversed[0]
+        );
+        assert_eq!(
+            3,
+            search.find_word("A", Directions::ALL).len()
+        );
+        assert!(search
+            .find_word("", Directions::ALL)
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_pattern() -> miette::Result<()> {
+        assert_eq!(4, Pattern::x_mas().orientations());
+        // symmetric patterns only count once
+        let plus = Pattern::parse(".A.\nAAA\n.A.", '.')?;
+        assert_eq!(1, plus.orientations());
+
+        let search = WordSearch::parse("S.S.\n.A..\nM.M.")?;
+        assert_eq!(
+            vec![PatternMatch {
+                top_left: IVec2::ZERO,
+                turns: 3
+            }],
+            search.find_pattern(&Pattern::x_mas())
+        );
+        // too big to fit anywhere
+        let wide = Pattern::parse("MASMAS", '.')?;
+        assert!(search.find_pattern(&wide).is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use std::{fmt::Write, ops::RangeInclusive};
+
+use aoc_common::generate::Generator;
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+/// Page ordering rules and the updates to
+/// check against them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub pages: usize,
+    /// How likely each pair of pages is to get a
+    /// rule.
+    pub density: f64,
+    pub updates: usize,
+    /// How many pages each update has, made odd
+    /// so it has a middle.
+    pub update_len: RangeInclusive<usize>,
+}
+
+impl Generator for Params {
+    /// `updates` updates over 49 pages, like the
+    /// real input, at least one.
+    fn sized(updates: usize) -> Self {
+        Self {
+            pages: 49,
+            density: 0.5,
+            updates: updates.max(1),
+            update_len: 5..=23,
+        }
+    }
+
+    /// Shuffles the pages into a hidden order
+    /// and only makes rules that agree with it,
+    /// so the rules can't have a cycle. About
+    /// half the updates follow the order.
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let pages = self.pages.max(2);
+        let mut order: Vec<u32> =
+            (10..10 + 10 * pages as u32).collect();
+        order.shuffle(rng);
+        order.truncate(pages);
+
+        let mut rules: Vec<(u32, u32)> = order
+            .iter()
+            .tuple_combinations()
+            .filter(|_| rng.gen_bool(self.density))
+            .map(|(before, after)| (*before, *after))
+            .collect();
+        if rules.is_empty() {
+            rules.push((order[0], order[1]));
+        }
+        rules.shuffle(rng);
+
+        let mut out = String::new();
+        for (before, after) in rules {
+            let _ = writeln!(out, "{before}|{after}");
+        }
+        out.push('\n');
+
+        for _ in 0..self.updates {
+            let len = rng
+                .gen_range(self.update_len.clone())
+                .clamp(1, pages);
+            let len =
+                if len % 2 == 0 { len - 1 } else { len };
+            let mut update: Vec<u32> = order
+                .choose_multiple(rng, len)
+                .copied()
+                .collect();
+            if rng.gen_bool(0.5) {
+                update.sort_by_key(|page| {
+                    order.iter().position(|p| p == page)
+                });
+            }
+            let _ = writeln!(
+                out,
+                "{}",
+                update.iter().join(",")
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::parse;
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        for seed in 0..5 {
+            let (rules, updates) =
+                parse(&Params::generate(seed, 20))?;
+            assert_eq!(20, updates.len());
+            for update in &updates {
+                assert_eq!(1, update.len() % 2);
+                rules.reorder(update)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,101 +1,18 @@
-use std::collections::HashMap;
+use aoc_common::Answer;
 
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    multi::{fold_many1, separated_list1},
-    sequence::{separated_pair, terminated},
-    IResult,
-};
-
-type Rules = HashMap<u32, Vec<u32>>;
-type Updates = Vec<Vec<u32>>;
-
-fn parse_rules(input: &str) -> IResult<&str, Rules> {
-    fold_many1(
-        terminated(
-            separated_pair(
-                complete::u32,
-                tag("|"),
-                complete::u32,
-            ),
-            line_ending,
-        ),
-        Rules::default,
-        |mut acc: Rules, (page, after)| {
-            acc.entry(page)
-                .and_modify(|afters| {
-                    afters.push(after);
-                })
-                .or_insert(vec![after]);
-            acc
-        },
-    )(input)
-}
-
-fn parse_updates(input: &str) -> IResult<&str, Updates> {
-    separated_list1(
-        line_ending,
-        separated_list1(tag(","), complete::u32),
-    )(input)
-}
-
-fn parse(input: &str) -> IResult<&str, (Rules, Updates)> {
-    let (input, rules) =
-        terminated(parse_rules, line_ending)(input)?;
-    let (input, updates) = parse_updates(input)?;
-
-    Ok((input, (rules, updates)))
-}
+use crate::rules::{middle, parse};
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let Ok((_, (rules, updates))) = parse(input) else {
-        panic!("Error parsing input!")
-    };
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let (rules, updates) = parse(input)?;
 
-    let indexes: Vec<usize> = updates
+    let result: u64 = updates
         .iter()
-        .enumerate()
-        .filter_map(|(index, original_update)| {
-            let mut current = original_update[0];
-            let mut update = &original_update[1..];
-            let mut before_pages = &original_update[0..0];
-
-            while before_pages.len()
-                != original_update.len()
-            {
-                if let Some(next_pages_rules) =
-                    rules.get(&current)
-                {
-                    if !next_pages_rules.iter().all(
-                        |page| !before_pages.contains(page),
-                    ) {
-                        return None;
-                    };
-                }
-
-                before_pages = &original_update
-                    [0..(before_pages.len() + 1)];
-
-                if let Some(page) = update.first() {
-                    update = &update[1..];
-                    current = *page;
-                }
-            }
-            Some(index)
-        })
-        .collect();
-
-    let result: u32 = indexes
-        .iter()
-        .map(|index| {
-            let mid = updates[*index].len() / 2;
-            updates[*index][mid]
-        })
+        .filter(|update| rules.check(update).is_ok())
+        .map(|update| u64::from(middle(update)))
         .sum();
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
 #[cfg(test)]
@@ -135,4 +52,15 @@ mod tests {
         assert_eq!("143", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_malformed_rule() {
+        let error =
+            process("47|53\n97|1x\n\n75,47").unwrap_err();
+        let error = error
+            .downcast_ref::<aoc_common::error::ParseError>()
+            .unwrap();
+        assert_eq!(6, error.offset());
+        assert_eq!("expected a line break", error.label());
+    }
 }
@@ -1,119 +1,20 @@
-use std::{cmp::Ordering, collections::HashMap};
+use aoc_common::Answer;
 
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    multi::{fold_many1, separated_list1},
-    sequence::{separated_pair, terminated},
-    IResult,
-};
-
-type Rules = HashMap<u32, Vec<u32>>;
-type Updates = Vec<Vec<u32>>;
-
-fn parse_rules(input: &str) -> IResult<&str, Rules> {
-    fold_many1(
-        terminated(
-            separated_pair(
-                complete::u32,
-                tag("|"),
-                complete::u32,
-            ),
-            line_ending,
-        ),
-        Rules::default,
-        |mut acc: Rules, (page, after)| {
-            acc.entry(page)
-                .and_modify(|afters| {
-                    afters.push(after);
-                })
-                .or_insert(vec![after]);
-            acc
-        },
-    )(input)
-}
-
-fn parse_updates(input: &str) -> IResult<&str, Updates> {
-    separated_list1(
-        line_ending,
-        separated_list1(tag(","), complete::u32),
-    )(input)
-}
-
-fn parse(input: &str) -> IResult<&str, (Rules, Updates)> {
-    let (input, rules) =
-        terminated(parse_rules, line_ending)(input)?;
-    let (input, updates) = parse_updates(input)?;
-
-    Ok((input, (rules, updates)))
-}
+use crate::rules::{middle, parse};
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let Ok((_, (rules, update))) = parse(input) else {
-        panic!("Error parsing input!")
-    };
-
-    let indexes: Vec<usize> = update
-        .iter()
-        .enumerate()
-        .filter_map(|(index, original_update)| {
-            let mut current = original_update[0];
-            let mut update = &original_update[1..];
-            let mut before_pages = &original_update[0..0];
-
-            while before_pages.len()
-                != original_update.len()
-            {
-                if let Some(next_pages_rules) =
-                    rules.get(&current)
-                {
-                    if !next_pages_rules.iter().all(
-                        |page| !before_pages.contains(page),
-                    ) {
-                        return Some(index);
-                    };
-                }
-
-                before_pages = &original_update
-                    [0..(before_pages.len() + 1)];
-
-                if let Some(page) = update.first() {
-                    update = &update[1..];
-                    current = *page;
-                }
-            }
-            None
-        })
-        .collect();
-
-    let corrected_updates: Vec<_> = indexes
-        .iter()
-        .map(|index| {
-            let mut update = update[*index].clone();
-            update.sort_by(|a, b| {
-                if rules
-                    .get(a)
-                    .is_some_and(|pages| pages.contains(b))
-                {
-                    Ordering::Less
-                } else {
-                    Ordering::Greater
-                }
-            });
-            update
-        })
-        .collect();
-
-    let result: &u32 = &corrected_updates
-        .iter()
-        .map(|update| {
-            let mid = update.len() / 2;
-            update[mid]
-        })
-        .sum();
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let (rules, updates) = parse(input)?;
+
+    let mut result: u64 = 0;
+    for update in &updates {
+        if rules.check(update).is_err() {
+            result +=
+                u64::from(middle(&rules.reorder(update)?));
+        }
+    }
 
-    Ok(result.to_string())
+    Ok(result.into())
 }
 
 #[cfg(test)]
@@ -153,4 +54,14 @@ mod tests {
         assert_eq!("123", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_cycle() {
+        let error =
+            process("1|2\n2|3\n3|1\n\n3,2,1").unwrap_err();
+        let error = error
+            .downcast_ref::<crate::rules::CycleError>()
+            .unwrap();
+        assert_eq!(vec![1, 2, 3, 1], error.cycle);
+    }
 }
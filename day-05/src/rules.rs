@@ -0,0 +1,380 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use aoc_common::error::{parse_all, IResult, ParseError};
+use itertools::Itertools;
+use miette::Diagnostic;
+use nom::{
+    character::complete::{self, line_ending},
+    multi::{many1, separated_list1},
+    sequence::{separated_pair, terminated},
+    Parser,
+};
+use nom_supreme::tag::complete::tag;
+use thiserror::Error;
+
+pub type Update = Vec<u32>;
+
+/// The page ordering rules, as a graph with an
+/// edge from each page to the pages that must
+/// come after it.
+#[derive(Debug, Default, Clone)]
+pub struct PageRules {
+    pairs: HashSet<(u32, u32)>,
+    after: HashMap<u32, Vec<u32>>,
+}
+
+/// A rule that an update breaks: `before` is
+/// printed after `after`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub before: u32,
+    pub after: u32,
+    /// Where `before` is in the update, the later
+    /// of the two pages.
+    pub index: usize,
+}
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+#[error(
+    "the rules for pages {} form a cycle: {}",
+    pages.iter().join(","),
+    cycle.iter().join(" -> ")
+)]
+#[diagnostic(
+    code(day_05::cycle),
+    help("no order of these pages satisfies every rule, so one of the rules in the cycle is wrong")
+)]
+pub struct CycleError {
+    pub pages: Vec<u32>,
+    /// Pages in rule order, starting and ending
+    /// with the same page.
+    pub cycle: Vec<u32>,
+}
+
+impl PageRules {
+    pub fn new(
+        rules: impl IntoIterator<Item = (u32, u32)>,
+    ) -> Self {
+        let mut page_rules = Self::default();
+        for (before, after) in rules {
+            if page_rules.pairs.insert((before, after)) {
+                page_rules
+                    .after
+                    .entry(before)
+                    .or_default()
+                    .push(after);
+            }
+        }
+        page_rules
+    }
+
+    /// Whether a rule says `before` must be
+    /// printed before `after`.
+    pub fn requires(
+        &self,
+        before: u32,
+        after: u32,
+    ) -> bool {
+        self.pairs.contains(&(before, after))
+    }
+
+    fn pages_after(&self, page: u32) -> &[u32] {
+        self.after.get(&page).map_or(&[], Vec::as_slice)
+    }
+
+    /// The first rule `update` breaks, going by
+    /// the later of the two pages.
+    pub fn check(
+        &self,
+        update: &[u32],
+    ) -> Result<(), Violation> {
+        // a repeated page counts from where it
+        // first appears
+        let mut positions: HashMap<u32, usize> =
+            HashMap::new();
+        for (i, page) in update.iter().enumerate() {
+            positions.entry(*page).or_insert(i);
+        }
+
+        for (index, page) in update.iter().enumerate() {
+            if let Some(before) = self
+                .pages_after(*page)
+                .iter()
+                .filter(|later| {
+                    positions
+                        .get(later)
+                        .is_some_and(|i| *i < index)
+                })
+                .min_by_key(|later| positions[later])
+            {
+                return Err(Violation {
+                    before: *page,
+                    after: *before,
+                    index,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Sorts the pages of `update` so that every
+    /// rule between them holds, ignoring rules
+    /// about pages that aren't in it.
+    pub fn reorder(
+        &self,
+        update: &[u32],
+    ) -> Result<Update, CycleError> {
+        let pages: HashSet<u32> =
+            update.iter().copied().collect();
+        let edges = |page: u32| {
+            self.pages_after(page)
+                .iter()
+                .copied()
+                .filter(|next| pages.contains(next))
+        };
+
+        let mut incoming: HashMap<u32, usize> =
+            pages.iter().map(|page| (*page, 0)).collect();
+        for page in &pages {
+            for next in edges(*page) {
+                *incoming.entry(next).or_default() += 1;
+            }
+        }
+
+        // keep pages in their original order when
+        // the rules don't decide
+        let mut ready: VecDeque<u32> = update
+            .iter()
+            .copied()
+            .filter(|page| incoming[page] == 0)
+            .unique()
+            .collect();
+        let mut ordered = Vec::with_capacity(update.len());
+        while let Some(page) = ready.pop_front() {
+            ordered.push(page);
+            for next in edges(page) {
+                let count = incoming
+                    .get_mut(&next)
+                    .expect("every page has a count");
+                *count -= 1;
+                if *count == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if ordered.len() < pages.len() {
+            let stuck: HashSet<u32> = pages
+                .into_iter()
+                .filter(|page| incoming[page] > 0)
+                .collect();
+            return Err(self.cycle_error(&stuck));
+        }
+
+        // pages printed twice stay twice
+        let counts = update.iter().counts();
+        Ok(ordered
+            .into_iter()
+            .flat_map(|page| {
+                std::iter::repeat_n(page, counts[&page])
+            })
+            .collect())
+    }
+
+    /// Every page in `stuck` has a rule from
+    /// another page in `stuck`, so walking those
+    /// rules backwards must eventually repeat.
+    fn cycle_error(
+        &self,
+        stuck: &HashSet<u32>,
+    ) -> CycleError {
+        let before = |page: u32| {
+            stuck
+                .iter()
+                .copied()
+                .filter(|other| self.requires(*other, page))
+                .min()
+        };
+
+        let mut walk = vec![*stuck.iter().min().unwrap()];
+        let start = loop {
+            let page = walk[walk.len() - 1];
+            let previous = before(page)
+                .expect("stuck pages have a rule");
+            if let Some(i) =
+                walk.iter().position(|p| *p == previous)
+            {
+                break i;
+            }
+            walk.push(previous);
+        };
+
+        // pages after the cycle are stuck too, but
+        // only the cycle itself is worth reporting
+        let mut cycle = walk.split_off(start);
+        cycle.reverse();
+        let first = cycle.iter().position_min().unwrap();
+        cycle.rotate_left(first);
+        cycle.push(cycle[0]);
+        CycleError {
+            pages: cycle[1..]
+                .iter()
+                .copied()
+                .sorted()
+                .collect(),
+            cycle,
+        }
+    }
+}
+
+fn rules(input: &str) -> IResult<PageRules> {
+    many1(terminated(
+        separated_pair(
+            complete::u32,
+            tag("|"),
+            complete::u32,
+        ),
+        line_ending,
+    ))
+    .map(PageRules::new)
+    .parse(input)
+}
+
+fn updates(input: &str) -> IResult<Vec<Update>> {
+    separated_list1(
+        line_ending,
+        separated_list1(tag(","), complete::u32),
+    )(input)
+}
+
+pub fn parse(
+    input: &str,
+) -> Result<(PageRules, Vec<Update>), ParseError> {
+    parse_all(
+        input,
+        separated_pair(rules, line_ending, updates),
+    )
+}
+
+/// The page in the middle of an update.
+pub fn middle(update: &[u32]) -> u32 {
+    update[update.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> PageRules {
+        PageRules::new([
+            (47, 53),
+            (97, 13),
+            (97, 61),
+            (97, 47),
+            (75, 29),
+            (61, 13),
+            (75, 53),
+            (29, 13),
+            (97, 29),
+            (53, 29),
+            (61, 53),
+            (97, 53),
+            (61, 29),
+            (47, 13),
+            (75, 47),
+            (97, 75),
+            (47, 61),
+            (75, 61),
+            (47, 29),
+            (75, 13),
+            (53, 13),
+        ])
+    }
+
+    #[test]
+    fn test_check() {
+        let rules = example();
+        assert!(rules.requires(47, 53));
+        assert!(!rules.requires(53, 47));
+        assert_eq!(
+            Ok(()),
+            rules.check(&[75, 47, 61, 53, 29])
+        );
+        assert_eq!(
+            Err(Violation {
+                before: 97,
+                after: 75,
+                index: 1
+            }),
+            rules.check(&[75, 97, 47, 61, 53])
+        );
+        assert_eq!(
+            Err(Violation {
+                before: 29,
+                after: 13,
+                index: 2
+            }),
+            rules.check(&[61, 13, 29])
+        );
+    }
+
+    #[test]
+    fn test_check_repeated_page() {
+        let rules = PageRules::new([(1, 2)]);
+        assert_eq!(
+            Err(Violation {
+                before: 1,
+                after: 2,
+                index: 1
+            }),
+            rules.check(&[2, 1, 2])
+        );
+        assert_eq!(Ok(()), rules.check(&[1, 2, 2]));
+    }
+
+    #[test]
+    fn test_reorder() {
+        let rules = example();
+        assert_eq!(
+            Ok(vec![97, 75, 47, 61, 53]),
+            rules.reorder(&[75, 97, 47, 61, 53])
+        );
+        assert_eq!(
+            Ok(vec![61, 29, 13]),
+            rules.reorder(&[61, 13, 29])
+        );
+        assert_eq!(
+            Ok(vec![97, 75, 47, 29, 13]),
+            rules.reorder(&[97, 13, 75, 29, 47])
+        );
+        // no rules between these, so nothing moves
+        assert_eq!(
+            Ok(vec![1, 2, 3]),
+            rules.reorder(&[1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let rules = PageRules::new([
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (3, 4),
+        ]);
+        let error =
+            rules.reorder(&[4, 3, 2, 1]).unwrap_err();
+        assert_eq!(vec![1, 2, 3], error.pages);
+        assert_eq!(vec![1, 2, 3, 1], error.cycle);
+        assert_eq!(
+            "the rules for pages 1,2,3 form a cycle: 1 -> 2 -> 3 -> 1",
+            error.to_string()
+        );
+
+        // the cycle needs all three pages
+        assert_eq!(
+            Ok(vec![3, 1, 4]),
+            rules.reorder(&[4, 3, 1])
+        );
+    }
+}
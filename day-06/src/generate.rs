@@ -0,0 +1,104 @@
+use aoc_common::generate::Generator;
+use aoc_grid::Grid;
+use rand::{seq::index, Rng};
+
+use crate::guard::{GuardSim, Patrol};
+
+/// A map of obstacles with the guard on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub width: usize,
+    pub height: usize,
+    pub obstacles: usize,
+}
+
+impl Generator for Params {
+    /// A square map with `obstacles` obstacles
+    /// covering about 5% of it, like the real
+    /// input.
+    fn sized(obstacles: usize) -> Self {
+        let side = ((obstacles as f64 * 20.0).sqrt()
+            as usize)
+            .max(5);
+        Self {
+            width: side,
+            height: side,
+            obstacles,
+        }
+    }
+
+    /// Places the obstacles and the guard at
+    /// random, trying again until the guard
+    /// walks off the map so part 1 has an
+    /// answer. Gives up on the obstacles after
+    /// enough tries.
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let cells = (self.width * self.height).max(1);
+        let width = self.width.max(1);
+        for _ in 0..100 {
+            let map = random_map(
+                rng,
+                self.obstacles.min(cells - 1),
+                cells,
+                width,
+            );
+            if exits(&map) {
+                return map;
+            }
+        }
+        random_map(rng, 0, cells, width)
+    }
+}
+
+fn random_map(
+    rng: &mut impl Rng,
+    obstacles: usize,
+    cells: usize,
+    width: usize,
+) -> String {
+    let mut map = vec!['.'; cells];
+    let mut picked =
+        index::sample(rng, cells, obstacles + 1)
+            .into_iter();
+    if let Some(guard) = picked.next() {
+        map[guard] = '^';
+    }
+    for obstacle in picked {
+        map[obstacle] = '#';
+    }
+
+    let mut out = String::new();
+    for row in map.chunks(width) {
+        out.extend(row);
+        out.push('\n');
+    }
+    out
+}
+
+fn exits(map: &str) -> bool {
+    Grid::parse(map).is_ok_and(|grid| {
+        GuardSim::new(&grid).is_ok_and(|sim| {
+            matches!(sim.run(), Patrol::Exited(_))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let params = Params::sized(50);
+        for seed in 0..5 {
+            let map = Params::generate(seed, 50);
+            let grid = Grid::parse(&map)?;
+            assert_eq!(params.width, grid.width());
+            assert_eq!(params.height, grid.height());
+            assert!(exits(&map));
+            assert_eq!(50, map.matches('#').count());
+        }
+        assert!(exits(&Params::generate(0, 0)));
+        Ok(())
+    }
+}
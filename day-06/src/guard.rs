@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use aoc_grid::Grid;
+use glam::IVec2;
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Why a map has no answer for the guard's
+/// patrol.
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+pub enum GuardError {
+    #[error("no guard on the map")]
+    #[diagnostic(
+        code(day_06::no_guard),
+        help("mark the guard with `^`, `>`, `v` or `<`")
+    )]
+    NoGuard,
+    #[error("the guard never leaves the map")]
+    #[diagnostic(code(day_06::never_leaves))]
+    NeverLeaves,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::Up),
+            '>' => Some(Self::Right),
+            'v' => Some(Self::Down),
+            '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    pub fn to_ivec(self) -> IVec2 {
+        match self {
+            Direction::Up => IVec2::NEG_Y,
+            Direction::Right => IVec2::X,
+            Direction::Down => IVec2::Y,
+            Direction::Left => IVec2::NEG_X,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+}
+
+/// How a guard's patrol ends.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Patrol {
+    /// The guard walked off the map after
+    /// stepping on these positions.
+    Exited(HashSet<IVec2>),
+    /// The guard came back to a position facing
+    /// the same way, so it never leaves.
+    Looped,
+}
+
+/// Walks the guard around a map, turning right at
+/// every `#`.
+#[derive(Debug, Clone)]
+pub struct GuardSim<'a> {
+    grid: &'a Grid<char>,
+    start: IVec2,
+    direction: Direction,
+    obstruction: Option<IVec2>,
+}
+
+impl<'a> GuardSim<'a> {
+    /// Finds the guard (`^`, `>`, `v` or `<`) on
+    /// `grid`.
+    pub fn new(
+        grid: &'a Grid<char>,
+    ) -> Result<Self, GuardError> {
+        grid.iter()
+            .find_map(|(start, c)| {
+                Direction::from_char(*c).map(|direction| {
+                    Self {
+                        grid,
+                        start,
+                        direction,
+                        obstruction: None,
+                    }
+                })
+            })
+            .ok_or(GuardError::NoGuard)
+    }
+
+    /// The same map with an extra `#` at `pos`.
+    pub fn with_obstruction(&self, pos: IVec2) -> Self {
+        Self {
+            obstruction: Some(pos),
+            ..self.clone()
+        }
+    }
+
+    pub fn start(&self) -> IVec2 {
+        self.start
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    fn is_blocked(&self, pos: IVec2) -> bool {
+        self.obstruction == Some(pos)
+            || self.grid.get(pos) == Some(&'#')
+    }
+
+    pub fn run(&self) -> Patrol {
+        let mut pos = self.start;
+        let mut direction = self.direction;
+        let mut visited = HashSet::from([pos]);
+        let mut states = HashSet::from([(pos, direction)]);
+
+        loop {
+            let next = pos + direction.to_ivec();
+            if !self.grid.in_bounds(next) {
+                return Patrol::Exited(visited);
+            }
+
+            if self.is_blocked(next) {
+                direction = direction.turn_right();
+            } else {
+                pos = next;
+                visited.insert(pos);
+            }
+
+            if !states.insert((pos, direction)) {
+                return Patrol::Looped;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    #[test]
+    fn test_finds_guard() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT)?;
+        let sim = GuardSim::new(&grid)?;
+        assert_eq!(IVec2::new(4, 6), sim.start());
+        assert_eq!(Direction::Up, sim.direction());
+
+        let grid = Grid::parse(&INPUT.replace('^', ">"))?;
+        let sim = GuardSim::new(&grid)?;
+        assert_eq!(Direction::Right, sim.direction());
+
+        let grid = Grid::parse(&INPUT.replace('^', "."))?;
+        assert_eq!(
+            GuardError::NoGuard,
+            GuardSim::new(&grid).unwrap_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_loops_with_obstruction() -> miette::Result<()> {
+        let grid = Grid::parse(INPUT)?;
+        let sim = GuardSim::new(&grid)?;
+        assert!(matches!(sim.run(), Patrol::Exited(_)));
+        assert_eq!(
+            Patrol::Looped,
+            sim.with_obstruction(IVec2::new(3, 6)).run()
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,30 @@
+use aoc_common::Answer;
+use aoc_grid::Grid;
+use rayon::prelude::*;
+
+use crate::guard::{GuardError, GuardSim, Patrol};
+
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String> {
-    todo!("day 01 - part 2");
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let grid = Grid::parse(input)?;
+    let sim = GuardSim::new(&grid)?;
+
+    // an obstruction off the original path can't
+    // change where the guard goes
+    let Patrol::Exited(path) = sim.run() else {
+        return Err(GuardError::NeverLeaves.into());
+    };
+
+    let result = path
+        .into_par_iter()
+        .filter(|pos| *pos != sim.start())
+        .filter(|pos| {
+            sim.with_obstruction(*pos).run()
+                == Patrol::Looped
+        })
+        .count();
+
+    Ok(result.into())
 }
 
 #[cfg(test)]
@@ -9,9 +33,17 @@ mod tests {
 
     #[test]
     fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
+        let input = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+        assert_eq!("6", process(input)?);
         Ok(())
     }
 }
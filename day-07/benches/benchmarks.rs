@@ -1,4 +1,8 @@
-use day_07::*;
+use aoc_common::{generate::Generator, Answer};
+use day_07::{
+    solver::{parse, solve_naive, PART2},
+    *,
+};
 
 fn main() {
     // Run registered benchmarks.
@@ -6,17 +10,47 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
+fn part1() -> Answer {
     part1::process(divan::black_box(include_str!(
         "../input1.txt",
     )))
-    .unwrap();
+    .unwrap()
 }
 
 #[divan::bench]
-fn part2() {
+fn part2() -> Answer {
     part2::process(divan::black_box(include_str!(
         "../input2.txt",
     )))
-    .unwrap();
-}
\ No newline at end of file
+    .unwrap()
+}
+
+/// Part 2 by trying every operator sequence, to
+/// compare against the backwards search.
+#[divan::bench]
+fn part2_naive() -> u64 {
+    parse(divan::black_box(include_str!(
+        "../input2.txt"
+    )))
+    .unwrap()
+    .iter()
+    .filter(|equation| {
+        solve_naive(equation, PART2).is_some()
+    })
+    .map(|equation| equation.test)
+    .sum()
+}
+
+#[divan::bench(args = [10, 100, 1000])]
+fn part1_generated(bencher: divan::Bencher, size: usize) {
+    bencher
+        .with_inputs(|| generate::Params::generate(0, size))
+        .bench_refs(|input| part1::process(input).unwrap());
+}
+
+#[divan::bench(args = [10, 100, 1000])]
+fn part2_generated(bencher: divan::Bencher, size: usize) {
+    bencher
+        .with_inputs(|| generate::Params::generate(0, size))
+        .bench_refs(|input| part2::process(input).unwrap());
+}
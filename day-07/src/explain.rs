@@ -0,0 +1,203 @@
+use std::fmt::Write;
+
+use crate::solver::{
+    render, solve_all, Equation, Operator,
+};
+
+/// Every calibration line with the operator
+/// assignments found to satisfy it.
+pub struct Explanation<'o> {
+    pub lines: Vec<(Equation, Vec<Vec<&'o dyn Operator>>)>,
+    limit: Option<usize>,
+}
+
+/// How many lines had no, exactly one, or more
+/// than one satisfying assignment.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub zero: usize,
+    pub one: usize,
+    pub many: usize,
+}
+
+impl<'o> Explanation<'o> {
+    /// Searches each line for its first `limit`
+    /// assignments, or all of them.
+    ///
+    /// At least two are kept for the tally, and
+    /// one more so the header can tell an exact
+    /// count from a cut-off one.
+    pub fn new(
+        equations: &[Equation],
+        operators: &[&'o dyn Operator],
+        limit: Option<usize>,
+    ) -> Self {
+        let search = limit.map(|limit| counted(limit) + 1);
+        Self {
+            limit,
+            lines: equations
+                .iter()
+                .map(|equation| {
+                    (
+                        equation.clone(),
+                        solve_all(
+                            equation, operators, search,
+                        ),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn tally(&self) -> Tally {
+        let mut tally = Tally::default();
+        for (_, solutions) in &self.lines {
+            match solutions.len() {
+                0 => tally.zero += 1,
+                1 => tally.one += 1,
+                _ => tally.many += 1,
+            }
+        }
+        tally
+    }
+
+    /// Lists each line and its first `limit`
+    /// assignments, or all of them, followed by
+    /// the tally.
+    pub fn render(&self) -> String {
+        let limit = self.limit.unwrap_or(usize::MAX);
+        let mut out = String::new();
+        for (equation, solutions) in &self.lines {
+            let numbers: Vec<_> = equation
+                .numbers
+                .iter()
+                .map(u64::to_string)
+                .collect();
+            let count = match self.limit.map(counted) {
+                Some(counted)
+                    if solutions.len() > counted =>
+                {
+                    format!("more than {counted} solutions")
+                }
+                _ if solutions.len() == 1 => {
+                    "1 solution".to_string()
+                }
+                _ => {
+                    format!("{} solutions", solutions.len())
+                }
+            };
+            let _ = writeln!(
+                out,
+                "{}: {} ({count})",
+                equation.test,
+                numbers.join(" "),
+            );
+            for ops in solutions.iter().take(limit) {
+                let _ = writeln!(
+                    out,
+                    "  {}",
+                    render(equation, ops)
+                );
+            }
+        }
+
+        let tally = self.tally();
+        let _ = write!(
+            out,
+            "{} lines with no solution, {} with one, {} with many",
+            tally.zero, tally.one, tally.many
+        );
+        out
+    }
+}
+
+/// How many assignments a line is counted up
+/// to when only `limit` are shown.
+fn counted(limit: usize) -> usize {
+    limit.max(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{parse, PART1, PART2};
+
+    const INPUT: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    #[test]
+    fn test_tally() -> miette::Result<()> {
+        let equations = parse(INPUT)?;
+        assert_eq!(
+            Tally {
+                zero: 6,
+                one: 2,
+                many: 1
+            },
+            Explanation::new(&equations, PART1, None)
+                .tally()
+        );
+        assert_eq!(
+            Tally {
+                zero: 3,
+                one: 5,
+                many: 1
+            },
+            Explanation::new(&equations, PART2, None)
+                .tally()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_limit() -> miette::Result<()> {
+        let equations = parse("3267: 81 40 27")?;
+        assert_eq!(
+            "3267: 81 40 27 (2 solutions)
+  81 * 40 + 27 = 3267
+0 lines with no solution, 0 with one, 1 with many",
+            Explanation::new(&equations, PART1, Some(1))
+                .render()
+        );
+        assert_eq!(
+            4,
+            Explanation::new(&equations, PART1, None)
+                .render()
+                .lines()
+                .count()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_limit_stops_the_search() -> miette::Result<()> {
+        // The trailing zero makes all 3^60 prefixes
+        // work, far too many to list.
+        let line = format!("0:{} 0", " 2".repeat(60));
+        let equations = parse(&line)?;
+        let explanation =
+            Explanation::new(&equations, PART2, Some(1));
+        assert_eq!(
+            Tally {
+                zero: 0,
+                one: 0,
+                many: 1
+            },
+            explanation.tally()
+        );
+        let rendered = explanation.render();
+        assert!(
+            rendered.contains("(more than 2 solutions)"),
+            "{rendered}"
+        );
+        assert_eq!(3, rendered.lines().count());
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use std::{fmt::Write, ops::RangeInclusive};
+
+use aoc_common::generate::Generator;
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::solver::{evaluate, Add, Operator, PART2};
+
+/// Calibration equations missing their
+/// operators.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub lines: usize,
+    /// How many numbers each line has.
+    pub operands: RangeInclusive<usize>,
+    /// How often a line's test value comes from
+    /// real operators rather than being nudged
+    /// off by one.
+    pub solvable: f64,
+}
+
+impl Generator for Params {
+    /// `lines` lines of 2 to 12 operands, like
+    /// the real input, at least one.
+    fn sized(lines: usize) -> Self {
+        Self {
+            lines: lines.max(1),
+            operands: 2..=12,
+            solvable: 0.5,
+        }
+    }
+
+    /// Picks numbers and part 2 operators at
+    /// random and writes down what they
+    /// evaluate to.
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let mut out = String::new();
+        for _ in 0..self.lines {
+            let len =
+                rng.gen_range(self.operands.clone()).max(1);
+            let numbers: Vec<u64> = (0..len)
+                .map(|_| rng.gen_range(1..1000))
+                .collect();
+            let operators: Vec<&dyn Operator> = (1..len)
+                .map(|_| *PART2.choose(rng).unwrap())
+                .collect();
+
+            // adding never gets near overflowing
+            let mut test = evaluate(&numbers, &operators)
+                .or_else(|| {
+                    evaluate(
+                        &numbers,
+                        &vec![
+                            &Add as &dyn Operator;
+                            len - 1
+                        ],
+                    )
+                })
+                .unwrap_or_default();
+            if !rng.gen_bool(self.solvable) {
+                test += 1;
+            }
+            let _ = writeln!(
+                out,
+                "{test}: {}",
+                numbers.iter().join(" ")
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::solver::{parse, solve};
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let always = Params {
+            solvable: 1.0,
+            ..Params::sized(50)
+        };
+        let equations =
+            parse(&always.generate_with(
+                &mut StdRng::seed_from_u64(1),
+            ))?;
+        assert_eq!(50, equations.len());
+        assert!(equations
+            .iter()
+            .all(
+                |equation| solve(equation, PART2).is_some()
+            ));
+        Ok(())
+    }
+}
@@ -1,2 +1,5 @@
+pub mod explain;
+pub mod generate;
 pub mod part1;
 pub mod part2;
+pub mod solver;
@@ -1,70 +1,32 @@
-use itertools::Itertools;
-
-use miette::miette;
-
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending, space1},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
-};
-
+use aoc_common::Answer;
 use rayon::prelude::*;
 
-const OPERATORS: [char; 2] = ['*', '+'];
-
-fn parse(
-    input: &str,
-) -> IResult<&str, Vec<(u64, Vec<u64>)>> {
-    separated_list1(
-        line_ending,
-        separated_pair(
-            complete::u64,
-            tag(": "),
-            separated_list1(space1, complete::u64),
-        ),
-    )(input)
-}
+use crate::{
+    explain::Explanation,
+    solver::{parse, solve, PART1},
+};
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let (_, parsed) = parse(input)
-        .map_err(|e| miette!("Error parsing: {e}"))?;
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let equations = parse(input)?;
 
-    let result: u64 = parsed
+    let result: u128 = equations
         .par_iter()
-        .filter_map(|(test, numbers)| {
-            let num_operators = numbers.len() - 1;
-
-            (0..num_operators)
-                .map(|_| OPERATORS)
-                .multi_cartesian_product()
-                .any(|seq| {
-                    let mut s = seq.iter();
-
-                    *test
-                        == numbers
-                            .iter()
-                            .copied()
-                            .reduce(|a, b| {
-                                match s.next().unwrap() {
-                                    '*' => a * b,
-                                    '+' => a + b,
-                                    _ => {
-                                        panic!(
-                                        "Invalid operator!"
-                                    )
-                                    }
-                                }
-                            })
-                            .unwrap()
-                })
-                .then_some(test)
-        })
+        .filter(|equation| solve(equation, PART1).is_some())
+        .map(|equation| u128::from(equation.test))
         .sum();
 
-    Ok(result.to_string())
+    Ok(result.into())
+}
+
+/// Lists up to `limit` satisfying operator
+/// assignments for every line.
+pub fn explain(
+    input: &str,
+    limit: Option<usize>,
+) -> miette::Result<String> {
+    let equations = parse(input)?;
+    Ok(Explanation::new(&equations, PART1, limit).render())
 }
 
 #[cfg(test)]
@@ -0,0 +1,788 @@
+use std::{collections::HashSet, fmt};
+
+use aoc_common::error::{parse_all, IResult, ParseError};
+use itertools::Itertools;
+use nom::{
+    character::complete::{self, line_ending, space1},
+    multi::separated_list1,
+    sequence::separated_pair,
+    Parser,
+};
+use nom_supreme::tag::complete::tag;
+
+/// A calibration equation with its operators
+/// missing, e.g. `3267: 81 40 27`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    pub test: u64,
+    pub numbers: Vec<u64>,
+}
+
+fn equations(input: &str) -> IResult<Vec<Equation>> {
+    separated_list1(
+        line_ending,
+        separated_pair(
+            complete::u64,
+            tag(": "),
+            separated_list1(space1, complete::u64),
+        )
+        .map(|(test, numbers)| Equation { test, numbers }),
+    )(input)
+}
+
+pub fn parse(
+    input: &str,
+) -> Result<Vec<Equation>, ParseError> {
+    parse_all(input, equations)
+}
+
+/// What undoing an operator tells us about its
+/// left hand side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Undo {
+    /// No left hand side gives the result.
+    Impossible,
+    /// Only this left hand side gives the result.
+    Exactly(u64),
+    /// Every left hand side gives the result,
+    /// like `x * 0 = 0`.
+    Any,
+}
+
+/// A binary operator, evaluated left to right
+/// like the puzzle does.
+pub trait Operator: Sync {
+    fn symbol(&self) -> &'static str;
+
+    /// `lhs op rhs`, or `None` if it overflows or
+    /// is undefined.
+    fn apply(&self, lhs: u64, rhs: u64) -> Option<u64>;
+
+    /// The `lhs` for which `lhs op rhs ==
+    /// result`.
+    fn undo(&self, result: u64, rhs: u64) -> Undo;
+}
+
+impl fmt::Debug for dyn Operator + '_ {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+fn exactly(lhs: Option<u64>) -> Undo {
+    lhs.map_or(Undo::Impossible, Undo::Exactly)
+}
+
+pub struct Add;
+
+impl Operator for Add {
+    fn symbol(&self) -> &'static str {
+        "+"
+    }
+
+    fn apply(&self, lhs: u64, rhs: u64) -> Option<u64> {
+        lhs.checked_add(rhs)
+    }
+
+    fn undo(&self, result: u64, rhs: u64) -> Undo {
+        exactly(result.checked_sub(rhs))
+    }
+}
+
+pub struct Mul;
+
+impl Operator for Mul {
+    fn symbol(&self) -> &'static str {
+        "*"
+    }
+
+    fn apply(&self, lhs: u64, rhs: u64) -> Option<u64> {
+        lhs.checked_mul(rhs)
+    }
+
+    fn undo(&self, result: u64, rhs: u64) -> Undo {
+        match (result, rhs) {
+            (0, 0) => Undo::Any,
+            (_, 0) => Undo::Impossible,
+            _ if result % rhs == 0 => {
+                Undo::Exactly(result / rhs)
+            }
+            _ => Undo::Impossible,
+        }
+    }
+}
+
+/// Joins the digits, so `12 || 345 = 12345`.
+pub struct Concat;
+
+impl Concat {
+    /// The power of ten that shifts `lhs` past
+    /// the digits of `rhs`, if it fits in a
+    /// `u64`.
+    fn shift(rhs: u64) -> Option<u64> {
+        10u64.checked_pow(
+            rhs.checked_ilog10().unwrap_or(0) + 1,
+        )
+    }
+}
+
+impl Operator for Concat {
+    fn symbol(&self) -> &'static str {
+        "||"
+    }
+
+    fn apply(&self, lhs: u64, rhs: u64) -> Option<u64> {
+        match Self::shift(rhs) {
+            Some(shift) => {
+                lhs.checked_mul(shift)?.checked_add(rhs)
+            }
+            // `0 || rhs` is still `rhs`
+            None => (lhs == 0).then_some(rhs),
+        }
+    }
+
+    fn undo(&self, result: u64, rhs: u64) -> Undo {
+        match Self::shift(rhs) {
+            Some(shift) if result % shift == rhs => {
+                Undo::Exactly(result / shift)
+            }
+            // only `0 || rhs` is that long
+            None if result == rhs => Undo::Exactly(0),
+            _ => Undo::Impossible,
+        }
+    }
+}
+
+/// Not in the puzzle, but shows how to add an
+/// operator. Results must not go below zero.
+pub struct Sub;
+
+impl Operator for Sub {
+    fn symbol(&self) -> &'static str {
+        "-"
+    }
+
+    fn apply(&self, lhs: u64, rhs: u64) -> Option<u64> {
+        lhs.checked_sub(rhs)
+    }
+
+    fn undo(&self, result: u64, rhs: u64) -> Undo {
+        exactly(result.checked_add(rhs))
+    }
+}
+
+/// Exponentiation, `2 ^ 10 = 1024`.
+pub struct Pow;
+
+impl Operator for Pow {
+    fn symbol(&self) -> &'static str {
+        "^"
+    }
+
+    fn apply(&self, lhs: u64, rhs: u64) -> Option<u64> {
+        lhs.checked_pow(rhs.try_into().ok()?)
+    }
+
+    fn undo(&self, result: u64, rhs: u64) -> Undo {
+        match (result, rhs) {
+            (1, 0) => Undo::Any,
+            (_, 0) => Undo::Impossible,
+            (0 | 1, _) | (_, 1) => Undo::Exactly(result),
+            // a root of an u64 with rhs >= 64 is
+            // below 2 and was handled above
+            _ => u32::try_from(rhs)
+                .ok()
+                .and_then(|exp| exact_root(result, exp))
+                .map_or(Undo::Impossible, Undo::Exactly),
+        }
+    }
+}
+
+/// The `root` with `root^exp == n`, if there is
+/// one, by binary search so it stays exact where
+/// a float root would round.
+fn exact_root(n: u64, exp: u32) -> Option<u64> {
+    let (mut low, mut high) = (0, n);
+    while low <= high {
+        let mid = low + (high - low) / 2;
+        match mid.checked_pow(exp) {
+            Some(power) if power == n => return Some(mid),
+            Some(power) if power < n => low = mid + 1,
+            _ => high = mid.checked_sub(1)?,
+        }
+    }
+    None
+}
+
+pub const PART1: &[&dyn Operator] = &[&Add, &Mul];
+pub const PART2: &[&dyn Operator] = &[&Add, &Mul, &Concat];
+
+/// Evaluates `numbers` left to right with
+/// `operators` between them.
+pub fn evaluate(
+    numbers: &[u64],
+    operators: &[&dyn Operator],
+) -> Option<u64> {
+    let (first, rest) = numbers.split_first()?;
+    if rest.len() != operators.len() {
+        return None;
+    }
+    rest.iter()
+        .zip(operators)
+        .try_fold(*first, |acc, (n, op)| op.apply(acc, *n))
+}
+
+/// Finds operators, one between each pair of
+/// numbers, that make the equation true.
+///
+/// Works backwards from the test value, undoing
+/// the last operator at each step, so most
+/// operators are ruled out without evaluating
+/// anything.
+pub fn solve<'o>(
+    equation: &Equation,
+    operators: &[&'o dyn Operator],
+) -> Option<Vec<&'o dyn Operator>> {
+    let mut assignment = vec![];
+    backwards(
+        equation.test,
+        &equation.numbers,
+        operators,
+        &mut assignment,
+    )
+    .then(|| {
+        assignment.reverse();
+        assignment
+    })
+}
+
+/// Pushes operators onto `assignment` from the
+/// last one back.
+///
+/// Keeps its own stack rather than recursing, so
+/// long lines can't overflow the real one, and
+/// remembers targets that led nowhere so lines
+/// like `1 1 1 ...` don't branch exponentially.
+fn backwards<'o>(
+    target: u64,
+    numbers: &[u64],
+    operators: &[&'o dyn Operator],
+    assignment: &mut Vec<&'o dyn Operator>,
+) -> bool {
+    if numbers.len() < 2 {
+        return numbers == [target];
+    }
+
+    let mut dead = HashSet::new();
+    // The target for the first `len` numbers, and
+    // the next operator to try undoing.
+    let mut stack = vec![(target, numbers.len(), 0)];
+    while let Some((target, len, next)) = stack.last_mut() {
+        let (target, len) = (*target, *len);
+        let op = match operators.get(*next) {
+            _ if len == 1 && numbers[0] == target => {
+                return true
+            }
+            Some(op) if len > 1 => op,
+            _ => {
+                dead.insert((target, len));
+                stack.pop();
+                if !stack.is_empty() {
+                    assignment.pop();
+                }
+                continue;
+            }
+        };
+        *next += 1;
+
+        match op.undo(target, numbers[len - 1]) {
+            Undo::Impossible => {}
+            Undo::Exactly(lhs) => {
+                if !dead.contains(&(lhs, len - 1)) {
+                    assignment.push(*op);
+                    stack.push((lhs, len - 1, 0));
+                }
+            }
+            Undo::Any => {
+                assignment.push(*op);
+                if forwards(
+                    &numbers[..len - 1],
+                    operators,
+                    assignment,
+                ) {
+                    return true;
+                }
+                assignment.pop();
+            }
+        }
+    }
+    false
+}
+
+/// Pushes any operators that evaluate `numbers`
+/// without overflowing, last one first.
+fn forwards<'o>(
+    numbers: &[u64],
+    operators: &[&'o dyn Operator],
+    assignment: &mut Vec<&'o dyn Operator>,
+) -> bool {
+    prefixes(numbers, operators, 1, |ops| {
+        assignment.extend(ops.iter().rev())
+    }) == 1
+}
+
+/// Calls `emit` with up to `limit` operator
+/// sequences that evaluate `numbers` without
+/// overflowing, and returns how many it found.
+///
+/// Works from the left, so a prefix that
+/// overflows rules out everything after it.
+fn prefixes<'o>(
+    numbers: &[u64],
+    operators: &[&'o dyn Operator],
+    limit: usize,
+    mut emit: impl FnMut(&[&'o dyn Operator]),
+) -> usize {
+    let Some((first, rest)) = numbers.split_first() else {
+        return 0;
+    };
+
+    let mut emitted = 0;
+    let mut dead = HashSet::new();
+    let mut ops = vec![];
+    // The value of the numbers before `rest[i]`,
+    // the next operator to apply to it, and how
+    // many had been emitted when it was reached.
+    let mut stack = vec![(*first, 0, 0, 0)];
+    while emitted < limit {
+        let Some((lhs, i, next, before)) = stack.last_mut()
+        else {
+            break;
+        };
+        let (lhs, i, before) = (*lhs, *i, *before);
+        let Some(rhs) = rest.get(i) else {
+            emit(&ops);
+            emitted += 1;
+            stack.pop();
+            ops.pop();
+            continue;
+        };
+        match operators.get(*next) {
+            Some(op) => {
+                *next += 1;
+                if let Some(value) =
+                    op.apply(lhs, *rhs).filter(|value| {
+                        !dead.contains(&(*value, i + 1))
+                    })
+                {
+                    ops.push(*op);
+                    stack.push((value, i + 1, 0, emitted));
+                }
+            }
+            None => {
+                if emitted == before {
+                    dead.insert((lhs, i));
+                }
+                stack.pop();
+                ops.pop();
+            }
+        }
+    }
+    emitted
+}
+
+/// Every assignment of operators that makes the
+/// equation true, or the first `limit` of them,
+/// found by the same backwards search as `solve`.
+pub fn solve_all<'o>(
+    equation: &Equation,
+    operators: &[&'o dyn Operator],
+    limit: Option<usize>,
+) -> Vec<Vec<&'o dyn Operator>> {
+    let limit = limit.unwrap_or(usize::MAX);
+    let numbers = &equation.numbers;
+    let mut found = vec![];
+    if numbers.len() < 2 {
+        if limit > 0 && numbers[..] == [equation.test] {
+            found.push(vec![]);
+        }
+        return found;
+    }
+
+    let mut dead = HashSet::new();
+    // The operators undone so far, last one first.
+    let mut suffix: Vec<&'o dyn Operator> = vec![];
+    // Like `backwards`, plus how many had been
+    // found when each target was reached.
+    let mut stack =
+        vec![(equation.test, numbers.len(), 0, 0)];
+    while found.len() < limit {
+        let Some((target, len, next, before)) =
+            stack.last_mut()
+        else {
+            break;
+        };
+        let (target, len, before) =
+            (*target, *len, *before);
+        if len == 1 {
+            if numbers[0] == target {
+                found.push(
+                    suffix.iter().rev().copied().collect(),
+                );
+            }
+            stack.pop();
+            suffix.pop();
+            continue;
+        }
+        let Some(op) = operators.get(*next) else {
+            if found.len() == before {
+                dead.insert((target, len));
+            }
+            stack.pop();
+            suffix.pop();
+            continue;
+        };
+        *next += 1;
+
+        match op.undo(target, numbers[len - 1]) {
+            Undo::Impossible => {}
+            Undo::Exactly(lhs) => {
+                if !dead.contains(&(lhs, len - 1)) {
+                    suffix.push(*op);
+                    stack.push((
+                        lhs,
+                        len - 1,
+                        0,
+                        found.len(),
+                    ));
+                }
+            }
+            Undo::Any => {
+                let rest = &numbers[..len - 1];
+                prefixes(
+                    rest,
+                    operators,
+                    limit - found.len(),
+                    |ops| {
+                        found.push(
+                            ops.iter()
+                                .chain([op])
+                                .chain(suffix.iter().rev())
+                                .copied()
+                                .collect(),
+                        )
+                    },
+                );
+            }
+        }
+    }
+    found
+}
+
+/// Tries every operator sequence from the left.
+/// Exponential, kept to check `solve` against.
+pub fn solve_naive<'o>(
+    equation: &Equation,
+    operators: &[&'o dyn Operator],
+) -> Option<Vec<&'o dyn Operator>> {
+    (0..equation.numbers.len().saturating_sub(1))
+        .map(|_| operators.iter().copied())
+        .multi_cartesian_product()
+        .find(|ops| {
+            evaluate(&equation.numbers, ops)
+                == Some(equation.test)
+        })
+}
+
+/// The equation with `operators` filled in, like
+/// `81 * 40 + 27 = 3267`.
+pub fn render(
+    equation: &Equation,
+    operators: &[&dyn Operator],
+) -> String {
+    let mut out = String::new();
+    for (i, n) in equation.numbers.iter().enumerate() {
+        if let Some(op) =
+            i.checked_sub(1).and_then(|i| operators.get(i))
+        {
+            out.push_str(&format!(" {} ", op.symbol()));
+        }
+        out.push_str(&n.to_string());
+    }
+    out.push_str(&format!(" = {}", equation.test));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::property;
+    use proptest::{
+        collection::vec, prelude::*,
+        test_runner::TestCaseError,
+    };
+
+    use super::*;
+
+    /// Every operator, including the ones the
+    /// puzzle doesn't use.
+    const ALL: &[&dyn Operator] =
+        &[&Add, &Mul, &Concat, &Sub, &Pow];
+
+    const INPUT: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    fn equation(test: u64, numbers: &[u64]) -> Equation {
+        Equation {
+            test,
+            numbers: numbers.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_returns_assignment() -> miette::Result<()> {
+        let equations = parse(INPUT)?;
+        let ops = solve(&equations[1], PART1).unwrap();
+        assert_eq!(
+            "81 * 40 + 27 = 3267",
+            render(&equations[1], &ops)
+        );
+        let ops = solve(&equations[4], PART2).unwrap();
+        assert_eq!(
+            "6 * 8 || 6 * 15 = 7290",
+            render(&equations[4], &ops)
+        );
+        assert!(solve(&equations[2], PART2).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_all() -> miette::Result<()> {
+        let equations = parse(INPUT)?;
+        let all = solve_all(&equations[1], PART1, None);
+        let rendered: Vec<_> = all
+            .iter()
+            .map(|ops| render(&equations[1], ops))
+            .collect();
+        assert_eq!(
+            vec![
+                "81 * 40 + 27 = 3267",
+                "81 + 40 * 27 = 3267"
+            ],
+            rendered
+        );
+        assert!(solve_all(&equations[2], PART2, None)
+            .is_empty());
+
+        // the zero makes every prefix work
+        let zero = equation(0, &[3, 4, 0]);
+        assert_eq!(2, solve_all(&zero, PART1, None).len());
+
+        // stops at the limit, keeping the same order
+        let first =
+            solve_all(&equations[1], PART1, Some(1));
+        assert_eq!(1, first.len());
+        assert_eq!(
+            rendered[0],
+            render(&equations[1], &first[0])
+        );
+        let mut numbers = vec![2; 60];
+        numbers.push(0);
+        let long = equation(0, &numbers);
+        assert_eq!(
+            5,
+            solve_all(&long, PART2, Some(5)).len()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_naive() -> miette::Result<()> {
+        for equation in parse(INPUT)? {
+            for operators in [PART1, PART2] {
+                let ops = solve(&equation, operators);
+                assert_eq!(
+                    solve_naive(&equation, operators)
+                        .is_some(),
+                    ops.is_some()
+                );
+                if let Some(ops) = ops {
+                    assert_eq!(
+                        Some(equation.test),
+                        evaluate(&equation.numbers, &ops)
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        for (test, numbers) in [
+            (0, &[5, 0][..]),
+            (0, &[u64::MAX, 2, 0]),
+            (7, &[7]),
+            (1024, &[2, 10]),
+            (3, &[10, 7]),
+            (5, &[0, 5]),
+            (u64::MAX, &[u64::MAX, 1, 1]),
+            (u64::MAX, &[0, u64::MAX]),
+        ] {
+            let equation = equation(test, numbers);
+            let ops = solve(&equation, ALL)
+                .unwrap_or_else(|| panic!("{equation:?}"));
+            assert_eq!(
+                Some(test),
+                evaluate(numbers, &ops),
+                "{}",
+                render(&equation, &ops)
+            );
+        }
+        // without `Add` to fall back on
+        let numbers = [0, u64::MAX];
+        let ops = solve(
+            &equation(u64::MAX, &numbers),
+            &[&Concat],
+        )
+        .expect("0 || u64::MAX");
+        assert_eq!(
+            Some(u64::MAX),
+            evaluate(&numbers, &ops)
+        );
+        assert!(
+            solve(&equation(1, &[u64::MAX, 2]), PART2)
+                .is_none()
+        );
+        assert!(solve(&equation(3, &[2]), PART2).is_none());
+    }
+
+    #[test]
+    fn test_pow_undo() {
+        let big = (1 << 60) + 100;
+        assert_eq!(Undo::Exactly(big), Pow.undo(big, 1));
+        assert_eq!(
+            Undo::Exactly(u64::MAX),
+            Pow.undo(u64::MAX, 1)
+        );
+        assert_eq!(
+            Undo::Exactly(3),
+            Pow.undo(3u64.pow(40), 40)
+        );
+        let square = u64::from(u32::MAX).pow(2);
+        assert_eq!(
+            Undo::Exactly(u64::from(u32::MAX)),
+            Pow.undo(square, 2)
+        );
+        assert_eq!(
+            Undo::Impossible,
+            Pow.undo(square + 1, 2)
+        );
+        assert_eq!(Undo::Impossible, Pow.undo(u64::MAX, 2));
+
+        let equation = equation(big, &[big, 1]);
+        assert!(solve_naive(&equation, &[&Pow]).is_some());
+        assert!(solve(&equation, &[&Pow]).is_some());
+    }
+
+    /// Lines that used to take exponential time
+    /// or overflow the stack.
+    #[test]
+    fn test_long_lines() {
+        let ones = equation(1_000_000, &[1; 40]);
+        assert!(solve(&ones, PART2).is_none());
+        let ones = equation(111, &[1; 40]);
+        assert!(solve(&ones, PART1).is_none());
+        assert!(solve(&ones, PART2).is_some());
+
+        let mut overflowing = vec![u64::MAX; 40];
+        overflowing.push(0);
+        assert!(solve(&equation(0, &overflowing), PART2)
+            .is_none());
+
+        let long = equation(2, &[1; 100_000]);
+        assert!(solve(&long, PART1).is_some());
+    }
+
+    /// Equations that are true for some random
+    /// operators about half the time, with any
+    /// test value otherwise.
+    fn equations() -> impl Strategy<Value = String> {
+        let equation = (
+            vec(1..=20u64, 1..=6),
+            vec(0..PART2.len(), 5),
+            prop_oneof![
+                Just(None),
+                (1..1000u64).prop_map(Some)
+            ],
+        )
+            .prop_map(|(numbers, ops, test)| {
+                let ops: Vec<_> = ops
+                    .iter()
+                    .take(numbers.len() - 1)
+                    .map(|i| PART2[*i])
+                    .collect();
+                let test = test
+                    .or_else(|| evaluate(&numbers, &ops))
+                    .unwrap_or(1);
+                format!(
+                    "{test}: {}",
+                    numbers.iter().join(" ")
+                )
+            });
+        vec(equation, 1..10)
+            .prop_map(|lines| lines.join("\n"))
+    }
+
+    #[test]
+    fn test_solve_matches_naive() -> miette::Result<()> {
+        property::check(
+            env!("CARGO_MANIFEST_DIR"),
+            "solve",
+            equations(),
+            |input| {
+                let equations =
+                    parse(input).map_err(|e| {
+                        TestCaseError::fail(e.to_string())
+                    })?;
+                for equation in &equations {
+                    for operators in [PART1, PART2, ALL] {
+                        let naive = solve_naive(
+                            equation, operators,
+                        );
+                        let fast =
+                            solve(equation, operators);
+                        prop_assert_eq!(
+                            naive.is_some(),
+                            fast.is_some(),
+                            "{:?}",
+                            equation
+                        );
+                        if let Some(ops) = fast {
+                            prop_assert_eq!(
+                                Some(equation.test),
+                                evaluate(
+                                    &equation.numbers,
+                                    &ops
+                                ),
+                                "{}",
+                                render(equation, &ops)
+                            );
+                        }
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+}
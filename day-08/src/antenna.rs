@@ -0,0 +1,353 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Write,
+};
+
+use aoc_common::error::ParseError;
+use aoc_grid::Grid;
+use glam::IVec2;
+use itertools::Itertools;
+
+/// Which points along the line through two
+/// antennas count as antinodes: those `n` times
+/// as far from one antenna as from the other
+/// side's, i.e. `a + n * (b - a)` and
+/// `b - n * (b - a)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Harmonics {
+    /// Only `n = 2`, as in part 1.
+    Double,
+    /// Every `n` that stays on the map, including
+    /// the antennas themselves, as in part 2.
+    Any,
+    Custom(Vec<u32>),
+}
+
+/// Every antenna on the map, grouped by
+/// frequency.
+#[derive(Debug, Clone)]
+pub struct AntennaMap {
+    frequencies: BTreeMap<char, Vec<IVec2>>,
+    width: i32,
+    height: i32,
+}
+
+/// What one frequency adds to the map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrequencyStats {
+    pub frequency: char,
+    pub antennas: usize,
+    pub antinodes: usize,
+    /// The antinodes other frequencies also
+    /// produce, with those frequencies, in
+    /// row-major order.
+    pub shared: Vec<(IVec2, Vec<char>)>,
+}
+
+impl AntennaMap {
+    /// Parses a map of `.` and antennas, whose
+    /// frequency is an ascii letter or digit.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse_with(input, |c| match c {
+            '.' => Some(None),
+            c if c.is_ascii_alphanumeric() => Some(Some(c)),
+            _ => None,
+        })?;
+        let mut frequencies: BTreeMap<char, Vec<IVec2>> =
+            BTreeMap::new();
+        for (pos, c) in grid.iter() {
+            if let Some(c) = c {
+                frequencies
+                    .entry(*c)
+                    .or_default()
+                    .push(pos);
+            }
+        }
+        Ok(Self {
+            frequencies,
+            width: grid.width() as i32,
+            height: grid.height() as i32,
+        })
+    }
+
+    pub fn frequencies(
+        &self,
+    ) -> impl Iterator<Item = (char, &[IVec2])> + '_ {
+        self.frequencies.iter().map(
+            |(frequency, positions)| {
+                (*frequency, positions.as_slice())
+            },
+        )
+    }
+
+    pub fn in_bounds(&self, pos: IVec2) -> bool {
+        (0..self.width).contains(&pos.x)
+            && (0..self.height).contains(&pos.y)
+    }
+
+    /// The in-bounds antinodes from every pair of
+    /// antennas on `frequency`.
+    pub fn antinodes_of(
+        &self,
+        frequency: char,
+        harmonics: &Harmonics,
+    ) -> HashSet<IVec2> {
+        let Some(positions) =
+            self.frequencies.get(&frequency)
+        else {
+            return HashSet::new();
+        };
+
+        let mut antinodes = HashSet::new();
+        for (a, b) in positions.iter().tuple_combinations()
+        {
+            for (from, step) in
+                [(*a, *b - *a), (*b, *a - *b)]
+            {
+                // `None` once it's too far out to
+                // represent, so off the map anyway
+                let at = |n: u32| {
+                    let n = i32::try_from(n).ok()?;
+                    Some(IVec2::new(
+                        step.x
+                            .checked_mul(n)?
+                            .checked_add(from.x)?,
+                        step.y
+                            .checked_mul(n)?
+                            .checked_add(from.y)?,
+                    ))
+                };
+                match harmonics {
+                    Harmonics::Double => {
+                        antinodes.extend(at(2))
+                    }
+                    Harmonics::Custom(multiples) => {
+                        antinodes.extend(
+                            multiples
+                                .iter()
+                                .filter_map(|n| at(*n)),
+                        )
+                    }
+                    Harmonics::Any => antinodes.extend(
+                        (0..).map_while(at).take_while(
+                            |pos| self.in_bounds(*pos),
+                        ),
+                    ),
+                }
+            }
+        }
+        antinodes.retain(|pos| self.in_bounds(*pos));
+        antinodes
+    }
+
+    /// Every in-bounds antinode of any frequency.
+    pub fn antinodes(
+        &self,
+        harmonics: &Harmonics,
+    ) -> HashSet<IVec2> {
+        self.frequencies
+            .keys()
+            .flat_map(|frequency| {
+                self.antinodes_of(*frequency, harmonics)
+            })
+            .collect()
+    }
+
+    pub fn stats(
+        &self,
+        harmonics: &Harmonics,
+    ) -> Vec<FrequencyStats> {
+        let antinodes: Vec<(char, HashSet<IVec2>)> = self
+            .frequencies
+            .keys()
+            .map(|frequency| {
+                (
+                    *frequency,
+                    self.antinodes_of(
+                        *frequency, harmonics,
+                    ),
+                )
+            })
+            .collect();
+
+        let mut sources: HashMap<IVec2, Vec<char>> =
+            HashMap::new();
+        for (frequency, positions) in &antinodes {
+            for pos in positions {
+                sources
+                    .entry(*pos)
+                    .or_default()
+                    .push(*frequency);
+            }
+        }
+
+        antinodes
+            .iter()
+            .map(|(frequency, positions)| {
+                let shared = positions
+                    .iter()
+                    .filter_map(|pos| {
+                        let others: Vec<char> = sources
+                            [pos]
+                            .iter()
+                            .copied()
+                            .filter(|other| {
+                                other != frequency
+                            })
+                            .collect();
+                        (!others.is_empty())
+                            .then_some((*pos, others))
+                    })
+                    .sorted_by_key(|(pos, _)| {
+                        (pos.y, pos.x)
+                    })
+                    .collect();
+                FrequencyStats {
+                    frequency: *frequency,
+                    antennas: self.frequencies[frequency]
+                        .len(),
+                    antinodes: positions.len(),
+                    shared,
+                }
+            })
+            .collect()
+    }
+
+    /// Lists each frequency's antinode counts and
+    /// up to `limit` of the antinodes it shares,
+    /// followed by the total.
+    pub fn explain(
+        &self,
+        harmonics: &Harmonics,
+        limit: Option<usize>,
+    ) -> String {
+        let mut out = String::new();
+        for stats in self.stats(harmonics) {
+            let _ = writeln!(
+                out,
+                "{}: {} antennas, {} antinodes, {} shared",
+                stats.frequency,
+                stats.antennas,
+                stats.antinodes,
+                stats.shared.len()
+            );
+            for (pos, others) in stats
+                .shared
+                .iter()
+                .take(limit.unwrap_or(usize::MAX))
+            {
+                let _ = writeln!(
+                    out,
+                    "  {},{} also from {}",
+                    pos.x,
+                    pos.y,
+                    others.iter().join(", ")
+                );
+            }
+        }
+        let _ = write!(
+            out,
+            "{} antinodes in total",
+            self.antinodes(harmonics).len()
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+    #[test]
+    fn test_harmonics() -> miette::Result<()> {
+        let map = AntennaMap::parse(INPUT)?;
+        assert_eq!(
+            14,
+            map.antinodes(&Harmonics::Double).len()
+        );
+        assert_eq!(
+            34,
+            map.antinodes(&Harmonics::Any).len()
+        );
+        assert_eq!(
+            map.antinodes(&Harmonics::Double),
+            map.antinodes(&Harmonics::Custom(vec![2]))
+        );
+        // n = 1 lands on the other antenna
+        assert_eq!(
+            HashSet::from([
+                IVec2::new(6, 5),
+                IVec2::new(8, 8),
+                IVec2::new(9, 9),
+            ]),
+            map.antinodes_of(
+                'A',
+                &Harmonics::Custom(vec![1])
+            )
+        );
+        assert!(map
+            .antinodes_of('x', &Harmonics::Any)
+            .is_empty());
+        assert_eq!(
+            0,
+            map.antinodes_of(
+                'A',
+                &Harmonics::Custom(vec![u32::MAX])
+            )
+            .len()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_other_characters() {
+        for (input, offset) in
+            [("..\n.é", 4), ("λ.\n..", 0), ("..\n#.", 3)]
+        {
+            let error =
+                AntennaMap::parse(input).unwrap_err();
+            assert_eq!(offset, error.offset());
+        }
+    }
+
+    #[test]
+    fn test_stats() -> miette::Result<()> {
+        let map = AntennaMap::parse(INPUT)?;
+        let stats = map.stats(&Harmonics::Double);
+        assert_eq!(
+            vec![('0', 4, 10, 1), ('A', 3, 5, 1)],
+            stats
+                .iter()
+                .map(|s| (
+                    s.frequency,
+                    s.antennas,
+                    s.antinodes,
+                    s.shared.len()
+                ))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(IVec2::new(3, 1), vec!['A'])],
+            stats[0].shared
+        );
+        assert!(map
+            .explain(&Harmonics::Double, None)
+            .ends_with(
+                "  3,1 also from 0\n14 antinodes in total"
+            ));
+        Ok(())
+    }
+}
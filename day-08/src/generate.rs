@@ -0,0 +1,79 @@
+use aoc_common::generate::Generator;
+use rand::{seq::index, Rng};
+
+const FREQUENCIES: &[u8] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// A map of antennas sharing a handful of
+/// frequencies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub width: usize,
+    pub height: usize,
+    pub antennas: usize,
+    /// How many different frequencies to share
+    /// the antennas between, at most 62.
+    pub frequencies: usize,
+}
+
+impl Generator for Params {
+    /// A `size` by `size` map about as crowded
+    /// as the real input.
+    fn sized(size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            width: size,
+            height: size,
+            antennas: (size * size / 12).max(2),
+            frequencies: (size / 2)
+                .clamp(1, FREQUENCIES.len()),
+        }
+    }
+
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let width = self.width.max(1);
+        let cells = width * self.height.max(1);
+        let frequencies =
+            self.frequencies.clamp(1, FREQUENCIES.len());
+
+        let mut map = vec!['.'; cells];
+        for cell in index::sample(
+            rng,
+            cells,
+            self.antennas.min(cells),
+        ) {
+            map[cell] = FREQUENCIES
+                [rng.gen_range(0..frequencies)]
+                as char;
+        }
+
+        let mut out = String::new();
+        for row in map.chunks(width) {
+            out.extend(row);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::antenna::{AntennaMap, Harmonics};
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let map =
+            AntennaMap::parse(&Params::generate(1, 50))?;
+        assert_eq!(
+            50 * 50 / 12,
+            map.frequencies()
+                .map(|(_, positions)| positions.len())
+                .sum::<usize>()
+        );
+        assert!(!map
+            .antinodes(&Harmonics::Double)
+            .is_empty());
+        Ok(())
+    }
+}
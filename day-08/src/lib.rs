@@ -1,2 +1,4 @@
+pub mod antenna;
+pub mod generate;
 pub mod part1;
 pub mod part2;
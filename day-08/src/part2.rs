@@ -1,97 +1,25 @@
-use std::iter::successors;
+use aoc_common::Answer;
 
-use glam::IVec2;
-use itertools::Itertools;
-use miette::miette;
-use nom::{
-    bytes::complete::take_till,
-    character::complete::satisfy, multi::many1,
-    sequence::preceded, IResult,
-};
-use nom_locate::{position, LocatedSpan};
-
-type Span<'a> = LocatedSpan<&'a str>;
-
-fn alphanum_pos(
-    input: Span,
-) -> IResult<Span, (IVec2, char)> {
-    let (input, pos) = position(input)?;
-    let x = pos.get_column() as i32 - 1;
-    let y = pos.location_line() as i32 - 1;
-    let (input, c) =
-        satisfy(|c| c.is_alphanumeric())(input)?;
-    Ok((input, (IVec2::new(x, y), c)))
-}
-
-fn parse(input: Span) -> IResult<Span, Vec<(IVec2, char)>> {
-    many1(preceded(
-        take_till(|c: char| c.is_alphanumeric()),
-        alphanum_pos,
-    ))(input)
-}
+use crate::antenna::{AntennaMap, Harmonics};
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
-    let y_bound = 0..height as i32;
-    let x_bound = 0..width as i32;
-
-    let (_, mut antennas) = parse(Span::new(input))
-        .map_err(|e| miette!("Error parsing: {e}"))?;
-    antennas.sort_by(|a, b| a.1.cmp(&b.1));
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let map = AntennaMap::parse(input)?;
 
-    let result = antennas
-        .chunk_by(|a, b| a.1 == b.1)
-        .flat_map(|chunk| {
-            chunk
-                .iter()
-                .combinations(2)
-                .flat_map(|ants| {
-                    let diff = ants[1].0 - ants[0].0;
+    let result = map.antinodes(&Harmonics::Any).len();
 
-                    let ret1: Vec<IVec2> = successors(
-                        Some(ants[0].0),
-                        |pos| {
-                            let new_pos = pos + diff;
-
-                            if x_bound.contains(&new_pos.x)
-                                && y_bound
-                                    .contains(&new_pos.y)
-                            {
-                                Some(new_pos)
-                            } else {
-                                None
-                            }
-                        },
-                    )
-                    .collect();
-
-                    let ret2: Vec<IVec2> = successors(
-                        Some(ants[1].0),
-                        |pos| {
-                            let new_pos = pos - diff;
-
-                            if x_bound.contains(&new_pos.x)
-                                && y_bound
-                                    .contains(&new_pos.y)
-                            {
-                                Some(new_pos)
-                            } else {
-                                None
-                            }
-                        },
-                    )
-                    .collect();
-
-                    [ret1, ret2]
-                })
-                .flatten()
-        })
-        .unique()
-        .count();
+    Ok(result.into())
+}
 
-    Ok(result.to_string())
+/// Counts every harmonic's antinodes per
+/// frequency and lists up to `limit` of the ones
+/// each shares with another frequency.
+pub fn explain(
+    input: &str,
+    limit: Option<usize>,
+) -> miette::Result<String> {
+    let map = AntennaMap::parse(input)?;
+    Ok(map.explain(&Harmonics::Any, limit))
 }
 
 #[cfg(test)]
@@ -0,0 +1,306 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
+
+use aoc_common::error::ParseError;
+
+/// A contiguous run of blocks belonging to one
+/// file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extent {
+    pub id: usize,
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Extent {
+    fn end(&self) -> usize {
+        self.start + self.len
+    }
+}
+
+/// The layout of files on the disk, parsed from
+/// the dense format where digits alternate
+/// between file and free span lengths.
+#[derive(Debug, Clone)]
+pub struct DiskMap {
+    files: Vec<Extent>,
+    /// Starts of the free spans, indexed by span
+    /// length so the leftmost span that fits a
+    /// file is a heap peek away.
+    free: Vec<BinaryHeap<Reverse<usize>>>,
+    size: usize,
+}
+
+impl DiskMap {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let dense = input.trim_end();
+        if dense.is_empty() {
+            return Err(ParseError::at(
+                input,
+                0,
+                "expected a disk map",
+            ));
+        }
+
+        let mut files = vec![];
+        let mut spans = vec![];
+        let mut start = 0;
+        for (i, c) in dense.char_indices() {
+            let len = c.to_digit(10).ok_or_else(|| {
+                ParseError::at(input, i, "expected a digit")
+            })? as usize;
+
+            if i % 2 == 1 {
+                // an empty file takes no space, so the
+                // spans either side of it are one
+                match spans.last_mut() {
+                    Some((prev, prev_len))
+                        if *prev + *prev_len == start =>
+                    {
+                        *prev_len += len
+                    }
+                    _ => spans.push((start, len)),
+                }
+            } else if len > 0 {
+                files.push(Extent {
+                    id: i / 2,
+                    start,
+                    len,
+                });
+            }
+            start += len;
+        }
+
+        let mut disk = Self {
+            files,
+            free: vec![],
+            size: start,
+        };
+        for (start, len) in spans {
+            disk.free_span(start, len);
+        }
+        Ok(disk)
+    }
+
+    pub fn files(&self) -> &[Extent] {
+        &self.files
+    }
+
+    fn free_span(&mut self, start: usize, len: usize) {
+        if len == 0 {
+            return;
+        }
+        if self.free.len() <= len {
+            self.free.resize_with(len + 1, BinaryHeap::new);
+        }
+        self.free[len].push(Reverse(start));
+    }
+
+    /// Every free span as `(start, len)`, ordered
+    /// by start.
+    fn drain_free(&mut self) -> Vec<(usize, usize)> {
+        let mut spans: Vec<_> = self
+            .free
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(len, starts)| {
+                starts
+                    .drain()
+                    .map(move |Reverse(start)| (start, len))
+            })
+            .collect();
+        spans.sort_unstable();
+        spans
+    }
+
+    /// Moves single blocks from the end of the
+    /// disk into the leftmost free blocks until
+    /// there are no gaps between files.
+    pub fn compact_blocks(&mut self) {
+        self.files.sort_unstable_by_key(|file| file.start);
+        let mut moved = vec![];
+
+        'spans: for (mut start, mut len) in
+            self.drain_free()
+        {
+            while len > 0 {
+                let Some(last) = self.files.last_mut()
+                else {
+                    break 'spans;
+                };
+                if last.start < start {
+                    break 'spans;
+                }
+
+                let blocks = len.min(last.len);
+                moved.push(Extent {
+                    id: last.id,
+                    start,
+                    len: blocks,
+                });
+                start += blocks;
+                len -= blocks;
+                last.len -= blocks;
+                if last.len == 0 {
+                    self.files.pop();
+                }
+            }
+        }
+
+        self.files.append(&mut moved);
+        self.files.sort_unstable_by_key(|file| file.start);
+
+        let gaps: Vec<_> = self
+            .files
+            .windows(2)
+            .map(|pair| {
+                (
+                    pair[0].end(),
+                    pair[1].start - pair[0].end(),
+                )
+            })
+            .collect();
+        for (start, len) in gaps {
+            self.free_span(start, len);
+        }
+    }
+
+    /// Moves each whole file, highest id first,
+    /// into the leftmost free span that fits it,
+    /// if that span is left of the file.
+    pub fn compact_files(&mut self) {
+        self.files
+            .sort_unstable_by_key(|file| Reverse(file.id));
+
+        for i in 0..self.files.len() {
+            let file = self.files[i];
+            let Some((len, start)) = self
+                .free
+                .iter()
+                .enumerate()
+                .skip(file.len)
+                .filter_map(|(len, starts)| {
+                    starts
+                        .peek()
+                        .map(|Reverse(start)| (len, *start))
+                })
+                .filter(|(_, start)| *start < file.start)
+                .min_by_key(|(_, start)| *start)
+            else {
+                continue;
+            };
+
+            self.free[len].pop();
+            self.free_span(
+                start + file.len,
+                len - file.len,
+            );
+            self.free_span(file.start, file.len);
+            self.files[i].start = start;
+        }
+
+        self.files.sort_unstable_by_key(|file| file.start);
+    }
+
+    /// The sum of each block's position times the
+    /// id of the file in it. Grows with the cube
+    /// of the disk's length, so it's kept in
+    /// a `u128`.
+    pub fn checksum(&self) -> u128 {
+        self.files
+            .iter()
+            .map(|file| {
+                let [id, start, len] =
+                    [file.id, file.start, file.len]
+                        .map(|n| n as u128);
+                let positions = len * start
+                    + len * len.saturating_sub(1) / 2;
+                id * positions
+            })
+            .sum()
+    }
+}
+
+impl fmt::Display for DiskMap {
+    /// Draws the disk like the puzzle does, with
+    /// file ids as digits and `.` for free
+    /// blocks.
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let mut blocks = vec!['.'; self.size];
+        for file in &self.files {
+            let c =
+                char::from_digit((file.id % 10) as u32, 10)
+                    .unwrap_or('?');
+            blocks[file.start..file.end()].fill(c);
+        }
+        write!(
+            f,
+            "{}",
+            blocks.into_iter().collect::<String>()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "2333133121414131402";
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let disk = DiskMap::parse("12345\n")?;
+        assert_eq!("0..111....22222", disk.to_string());
+        assert_eq!(3, disk.files().len());
+
+        let error = DiskMap::parse("12a45").unwrap_err();
+        assert_eq!(2, error.offset());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_blocks() -> miette::Result<()> {
+        let mut disk = DiskMap::parse("12345")?;
+        disk.compact_blocks();
+        assert_eq!("022111222......", disk.to_string());
+
+        let mut disk = DiskMap::parse(INPUT)?;
+        disk.compact_blocks();
+        assert_eq!(
+            "0099811188827773336446555566..............",
+            disk.to_string()
+        );
+        assert_eq!(1928, disk.checksum());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_files() -> miette::Result<()> {
+        let mut disk = DiskMap::parse(INPUT)?;
+        disk.compact_files();
+        assert_eq!(
+            "00992111777.44.333....5555.6666.....8888..",
+            disk.to_string()
+        );
+        assert_eq!(2858, disk.checksum());
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_files() -> miette::Result<()> {
+        let disk = DiskMap::parse("12023")?;
+        assert_eq!("0....222", disk.to_string());
+
+        let mut blocks = disk.clone();
+        blocks.compact_blocks();
+        assert_eq!("0222....", blocks.to_string());
+
+        let mut files = disk.clone();
+        files.compact_files();
+        assert_eq!("0222....", files.to_string());
+        assert_eq!(12, files.checksum());
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use aoc_common::generate::Generator;
+use rand::Rng;
+
+/// A dense disk map of alternating file and
+/// free span lengths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    /// How many digits the disk map has.
+    pub len: usize,
+}
+
+impl Generator for Params {
+    fn sized(len: usize) -> Self {
+        Self { len: len.max(1) }
+    }
+
+    /// Files are 1 to 9 blocks long and the free
+    /// spans between them 0 to 9.
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let mut out: String = (0..self.len)
+            .map(|i| {
+                let low = if i % 2 == 0 { 1 } else { 0 };
+                char::from(b'0' + rng.gen_range(low..=9))
+            })
+            .collect();
+        out.push('\n');
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disk::DiskMap;
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let disk =
+            DiskMap::parse(&Params::generate(1, 99))?;
+        assert_eq!(50, disk.files().len());
+        assert!(disk
+            .files()
+            .iter()
+            .all(|file| file.len > 0));
+        Ok(())
+    }
+}
@@ -1,69 +1,12 @@
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String> {
-    let high_index: u32 = input
-        .chars()
-        .map(|c| c.to_digit(10).unwrap_or_default())
-        .sum();
-
-    let mut reverse = (0..input.len())
-        .rev()
-        .zip(input.chars().rev())
-        .scan(
-            high_index,
-            |base_index, (compressed_index, c)| {
-                let num_indices =
-                    c.to_digit(10).unwrap_or_default();
-                *base_index -= num_indices;
-
-                Some(
-                    (*base_index
-                        ..(*base_index + num_indices))
-                        .rev()
-                        .filter_map(move |i| {
-                            (compressed_index % 2 == 0)
-                                .then_some((
-                                    i,
-                                    compressed_index / 2,
-                                ))
-                        }),
-                )
-            },
-        )
-        .flatten();
-
-    let mut base_index = 0;
-    let mut sum = 0;
-    let mut last_uncompressed_index = u32::MAX;
-
-    for (compressed_index, c) in input.chars().enumerate() {
-        let num_indices =
-            c.to_digit(10).unwrap_or_default() as usize;
-        let file_id = compressed_index / 2;
+use aoc_common::Answer;
 
-        for uncompressed_index in
-            base_index..(base_index + num_indices)
-        {
-            if uncompressed_index
-                >= last_uncompressed_index as usize
-            {
-                break;
-            }
+use crate::disk::DiskMap;
 
-            if compressed_index % 2 == 0 {
-                sum += uncompressed_index * file_id;
-            } else {
-                let (rev_uncompressed_index, file_id) =
-                    reverse.next().unwrap();
-
-                sum += uncompressed_index * file_id;
-                last_uncompressed_index =
-                    rev_uncompressed_index;
-            }
-        }
-        base_index += num_indices;
-    }
-
-    Ok(sum.to_string())
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let mut disk = DiskMap::parse(input)?;
+    disk.compact_blocks();
+    Ok(disk.checksum().into())
 }
 
 #[cfg(test)]
@@ -1,6 +1,12 @@
+use aoc_common::Answer;
+
+use crate::disk::DiskMap;
+
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String> {
-    todo!("day 01 - part 2");
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let mut disk = DiskMap::parse(input)?;
+    disk.compact_files();
+    Ok(disk.checksum().into())
 }
 
 #[cfg(test)]
@@ -9,9 +15,8 @@ mod tests {
 
     #[test]
     fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
+        let input = "2333133121414131402";
+        assert_eq!("2858", process(input)?);
         Ok(())
     }
 }
@@ -1,3 +1,4 @@
+use aoc_common::{generate::Generator, Answer};
 use day_11::*;
 
 fn main() {
@@ -6,17 +7,69 @@ fn main() {
 }
 
 #[divan::bench]
-fn part1() {
+fn part1() -> Answer {
     part1::process(divan::black_box(include_str!(
         "../input1.txt",
     )))
+    .unwrap()
+}
+
+/// Part 1 the way it was first solved, keeping
+/// every stone, to compare against `count_after`.
+#[divan::bench]
+fn part1_naive() -> usize {
+    let mut stones = stones::parse(divan::black_box(
+        include_str!("../input1.txt"),
+    ))
     .unwrap();
+    for _ in 0..25 {
+        stones = transform(&stones);
+    }
+    stones.len()
+}
+
+/// The original blink, splitting numbers through
+/// their decimal strings.
+fn transform(numbers: &[u64]) -> Vec<u64> {
+    numbers
+        .iter()
+        .flat_map(|&n| {
+            let tmp = n.to_string();
+            let len = tmp.len();
+            if n == 0 {
+                Vec::from([1])
+            } else if len % 2 == 0 {
+                let mid = len / 2;
+                let (l, r) = tmp.split_at(mid);
+                Vec::from([
+                    l.parse::<u64>().unwrap(),
+                    r.parse::<u64>().unwrap(),
+                ])
+            } else {
+                Vec::from([n * 2024])
+            }
+        })
+        .collect()
 }
 
 #[divan::bench]
-fn part2() {
+fn part2() -> Answer {
     part2::process(divan::black_box(include_str!(
         "../input2.txt",
     )))
-    .unwrap();
-}
\ No newline at end of file
+    .unwrap()
+}
+
+#[divan::bench(args = [10, 100, 1000])]
+fn part1_generated(bencher: divan::Bencher, size: usize) {
+    bencher
+        .with_inputs(|| generate::Params::generate(0, size))
+        .bench_refs(|input| part1::process(input).unwrap());
+}
+
+#[divan::bench(args = [10, 100, 1000])]
+fn part2_generated(bencher: divan::Bencher, size: usize) {
+    bencher
+        .with_inputs(|| generate::Params::generate(0, size))
+        .bench_refs(|input| part2::process(input).unwrap());
+}
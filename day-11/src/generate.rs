@@ -0,0 +1,45 @@
+use aoc_common::generate::Generator;
+use itertools::Itertools;
+use rand::Rng;
+
+/// A line of engraved stones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    pub stones: usize,
+    /// The largest number engraved on a stone.
+    pub max: u64,
+}
+
+impl Generator for Params {
+    /// `stones` stones with numbers up to seven
+    /// digits, like the real input, at least one.
+    fn sized(stones: usize) -> Self {
+        Self {
+            stones: stones.max(1),
+            max: 9_999_999,
+        }
+    }
+
+    fn generate_with(&self, rng: &mut impl Rng) -> String {
+        let stones = (0..self.stones)
+            .map(|_| rng.gen_range(0..=self.max))
+            .join(" ");
+        format!("{stones}\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stones::parse;
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let stones = parse(&Params::generate(1, 20))?;
+        assert_eq!(20, stones.len());
+        assert!(stones
+            .iter()
+            .all(|stone| *stone <= 9_999_999));
+        Ok(())
+    }
+}
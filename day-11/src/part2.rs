@@ -1,6 +1,11 @@
+use aoc_common::Answer;
+
+use crate::stones::{count_after, parse};
+
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String> {
-    todo!("day 01 - part 2");
+pub fn process(input: &str) -> miette::Result<Answer> {
+    let stones = parse(input)?;
+    Ok(count_after(&stones, 75)?.into())
 }
 
 #[cfg(test)]
@@ -9,9 +14,8 @@ mod tests {
 
     #[test]
     fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
+        let input = "125 17";
+        assert_eq!("65601038650482", process(input)?);
         Ok(())
     }
 }
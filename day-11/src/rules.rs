@@ -0,0 +1,292 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// A stone that would change into a number too
+/// big to engrave.
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+#[error(
+    "stone {stone} changes into a number past {}",
+    u64::MAX
+)]
+#[diagnostic(
+    code(day_11::overflow),
+    help("the rules only work with stones that stay within a `u64`")
+)]
+pub struct OverflowError {
+    pub stone: u64,
+}
+
+/// One way a stone can change when you blink.
+pub trait StoneRule {
+    /// The stones `stone` turns into, or `None`
+    /// when this rule doesn't apply to it.
+    fn apply(
+        &self,
+        stone: u64,
+    ) -> Result<Option<Vec<u64>>, OverflowError>;
+}
+
+/// A stone engraved with `from` is replaced by
+/// one engraved with `to`.
+#[derive(Debug, Clone, Copy)]
+pub struct Replace {
+    pub from: u64,
+    pub to: u64,
+}
+
+impl StoneRule for Replace {
+    fn apply(
+        &self,
+        stone: u64,
+    ) -> Result<Option<Vec<u64>>, OverflowError> {
+        Ok((stone == self.from).then(|| vec![self.to]))
+    }
+}
+
+/// A stone whose number has a multiple of `parts`
+/// digits in `base` is split into `parts` stones,
+/// one per group of digits.
+#[derive(Debug, Clone, Copy)]
+pub struct Split {
+    base: u64,
+    parts: u32,
+}
+
+impl Split {
+    /// `None` unless there are at least two
+    /// digits to a base and two parts to
+    /// split into.
+    pub fn new(base: u64, parts: u32) -> Option<Self> {
+        (base >= 2 && parts >= 2)
+            .then_some(Self { base, parts })
+    }
+}
+
+impl StoneRule for Split {
+    fn apply(
+        &self,
+        stone: u64,
+    ) -> Result<Option<Vec<u64>>, OverflowError> {
+        Ok(split_digits(
+            stone, self.base, self.parts,
+        ))
+    }
+}
+
+/// Every stone's number is multiplied by the
+/// factor.
+#[derive(Debug, Clone, Copy)]
+pub struct Multiply(pub u64);
+
+impl StoneRule for Multiply {
+    fn apply(
+        &self,
+        stone: u64,
+    ) -> Result<Option<Vec<u64>>, OverflowError> {
+        stone
+            .checked_mul(self.0)
+            .map(|n| Some(vec![n]))
+            .ok_or(OverflowError { stone })
+    }
+}
+
+/// An ordered list of rules where the first rule
+/// that applies wins. A stone no rule applies to
+/// stays as it is.
+pub struct Rules(Vec<Box<dyn StoneRule>>);
+
+impl Rules {
+    pub fn new(rules: Vec<Box<dyn StoneRule>>) -> Self {
+        Self(rules)
+    }
+
+    /// The rules from the puzzle: 0 becomes 1, an
+    /// even number of digits splits in half and
+    /// anything else is multiplied by 2024.
+    pub fn puzzle() -> Self {
+        Self::new(vec![
+            Box::new(Replace { from: 0, to: 1 }),
+            Box::new(
+                Split::new(10, 2).expect(
+                    "halving base 10 digits is valid",
+                ),
+            ),
+            Box::new(Multiply(2024)),
+        ])
+    }
+
+    pub fn apply(
+        &self,
+        stone: u64,
+    ) -> Result<Vec<u64>, OverflowError> {
+        for rule in &self.0 {
+            if let Some(stones) = rule.apply(stone)? {
+                return Ok(stones);
+            }
+        }
+        Ok(vec![stone])
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::puzzle()
+    }
+}
+
+/// How many digits `n` has when written in
+/// `base`, or `None` for bases below 2. Zero has
+/// one digit.
+pub fn digit_count(mut n: u64, base: u64) -> Option<u32> {
+    if base < 2 {
+        return None;
+    }
+    let mut digits = 1;
+    while n >= base {
+        n /= base;
+        digits += 1;
+    }
+    Some(digits)
+}
+
+/// Splits the digits of `n` in `base` into
+/// `parts` equally sized numbers, most
+/// significant first, or `None` when the digits
+/// don't divide evenly or `base` is below 2.
+pub fn split_digits(
+    n: u64,
+    base: u64,
+    parts: u32,
+) -> Option<Vec<u64>> {
+    let digits = digit_count(n, base)?;
+    if parts < 2 || digits % parts != 0 {
+        return None;
+    }
+
+    let divisor = base.checked_pow(digits / parts)?;
+    let mut rest = n;
+    let mut split: Vec<u64> = (0..parts)
+        .map(|_| {
+            let part = rest % divisor;
+            rest /= divisor;
+            part
+        })
+        .collect();
+    split.reverse();
+    Some(split)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stones::growth;
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(Some(1), digit_count(0, 10));
+        assert_eq!(Some(4), digit_count(2024, 10));
+        assert_eq!(Some(4), digit_count(0b1010, 2));
+        assert_eq!(None, digit_count(5, 1));
+        assert_eq!(None, digit_count(5, 0));
+        assert_eq!(
+            Some(vec![20, 24]),
+            split_digits(2024, 10, 2)
+        );
+        assert_eq!(
+            Some(vec![10, 0]),
+            split_digits(1000, 10, 2)
+        );
+        assert_eq!(
+            Some(vec![12, 34, 56]),
+            split_digits(123456, 10, 3)
+        );
+        assert_eq!(None, split_digits(123, 10, 2));
+        assert_eq!(
+            Some(vec![0b10, 0b11]),
+            split_digits(0b1011, 2, 2)
+        );
+    }
+
+    #[test]
+    fn test_puzzle_rules() -> miette::Result<()> {
+        let rules = Rules::puzzle();
+        assert_eq!(vec![1], rules.apply(0)?);
+        assert_eq!(vec![1, 7], rules.apply(17)?);
+        assert_eq!(vec![253000], rules.apply(125)?);
+        // 19 digits, and too big to multiply
+        let big = u64::MAX / 2;
+        assert_eq!(
+            Err(OverflowError { stone: big }),
+            rules.apply(big)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        // 17 digits times 2024 is past `u64::MAX`
+        let stone = 99_999_999_999_999_999;
+        assert_eq!(
+            Err(OverflowError { stone }),
+            growth(&Rules::puzzle(), &[stone], 1)
+        );
+        assert!(
+            crate::part1::process("99999999999999999")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_variant_growth() -> miette::Result<()> {
+        let puzzle =
+            growth(&Rules::puzzle(), &[125, 17], 25)?;
+        assert_eq!(Some(&55312), puzzle.last());
+
+        // without the split nothing ever multiplies
+        let no_split = Rules::new(vec![
+            Box::new(Replace { from: 0, to: 1 }),
+            Box::new(Multiply(2024)),
+        ]);
+        assert_eq!(
+            vec![2; 6],
+            growth(&no_split, &[125, 17], 5)?
+        );
+
+        let three_way = Rules::new(vec![
+            Box::new(Replace { from: 0, to: 1 }),
+            Box::new(Split::new(10, 3).unwrap()),
+            Box::new(Multiply(2024)),
+        ]);
+        // fewer numbers have a multiple of three
+        // digits, so fewer stones split and the
+        // rest keep growing until they overflow
+        assert_eq!(
+            vec![2, 4, 4, 4, 6, 6, 6],
+            growth(&three_way, &[125, 17], 6)?
+        );
+        assert!(growth(&three_way, &[125, 17], 7).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_needs_a_real_base() -> miette::Result<()>
+    {
+        assert!(Split::new(0, 2).is_none());
+        assert!(Split::new(1, 2).is_none());
+        assert!(Split::new(10, 1).is_none());
+        assert_eq!(None, split_digits(12, 0, 2));
+        assert_eq!(None, split_digits(12, 1, 2));
+
+        let binary = Rules::new(vec![
+            Box::new(Replace { from: 0, to: 1 }),
+            Box::new(Split::new(2, 2).unwrap()),
+            Box::new(Multiply(3)),
+        ]);
+        // 1 -> 3 (0b11) -> 1, 1 -> 3, 3 -> 1, 1, 1, 1
+        assert_eq!(
+            vec![1, 1, 2, 2, 4],
+            growth(&binary, &[1], 4)?
+        );
+        Ok(())
+    }
+}
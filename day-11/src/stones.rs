@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use aoc_common::error::{parse_all, IResult, ParseError};
+use itertools::Itertools;
+use nom::{
+    character::complete::{self, space1},
+    multi::separated_list1,
+};
+
+use crate::rules::{OverflowError, Rules};
+
+fn stones(input: &str) -> IResult<Vec<u64>> {
+    separated_list1(space1, complete::u64)(input)
+}
+
+/// The engraved numbers on the stones, left to
+/// right.
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    parse_all(input, stones)
+}
+
+/// Blinks once, keeping every stone in order.
+/// Grows exponentially, so only usable for a few
+/// dozen blinks.
+pub fn blink(
+    stones: &[u64],
+) -> Result<Vec<u64>, OverflowError> {
+    blink_with(&Rules::puzzle(), stones)
+}
+
+pub fn blink_with(
+    rules: &Rules,
+    stones: &[u64],
+) -> Result<Vec<u64>, OverflowError> {
+    stones
+        .iter()
+        .map(|&n| rules.apply(n))
+        .flatten_ok()
+        .collect()
+}
+
+/// How many stones there are after `blinks`
+/// blinks.
+pub fn count_after(
+    stones: &[u64],
+    blinks: usize,
+) -> Result<u128, OverflowError> {
+    count_after_with(&Rules::puzzle(), stones, blinks)
+}
+
+/// `count_after` for any set of rules.
+pub fn count_after_with(
+    rules: &Rules,
+    stones: &[u64],
+    blinks: usize,
+) -> Result<u128, OverflowError> {
+    Ok(growth(rules, stones, blinks)?
+        .last()
+        .copied()
+        .unwrap_or_default())
+}
+
+/// The number of stones before the first blink
+/// and after each of the `blinks` blinks.
+///
+/// The order of stones never affects how a stone
+/// changes, so only the count of each distinct
+/// number is kept between blinks. A `u64` count
+/// overflows after 75 blinks of enough stones.
+///
+/// Fails on any stone whose number grows too
+/// big to hold.
+pub fn growth(
+    rules: &Rules,
+    stones: &[u64],
+    blinks: usize,
+) -> Result<Vec<u128>, OverflowError> {
+    let mut counts: HashMap<u64, u128> = HashMap::new();
+    for &stone in stones {
+        *counts.entry(stone).or_default() += 1;
+    }
+
+    let mut totals = vec![stones.len() as u128];
+    for _ in 0..blinks {
+        let mut next = HashMap::with_capacity(counts.len());
+        for (stone, count) in counts {
+            for changed in rules.apply(stone)? {
+                *next.entry(changed).or_default() += count;
+            }
+        }
+        counts = next;
+        totals.push(counts.values().sum());
+    }
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::property;
+    use itertools::Itertools;
+    use proptest::{
+        collection::vec, prelude::*,
+        test_runner::TestCaseError,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_blink() -> miette::Result<()> {
+        assert_eq!(
+            vec![1, 2024, 1, 0, 9, 9, 2021976],
+            blink(&[0, 1, 10, 99, 999])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_after_many_stones() -> miette::Result<()>
+    {
+        // more than fits in a `u64`
+        assert_eq!(
+            count_after(&[0], 75)? * 1_000_000,
+            count_after(&vec![0; 1_000_000], 75)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_after_matches_blink() -> miette::Result<()>
+    {
+        let mut stones = vec![125, 17];
+        for blinks in 0..=25 {
+            assert_eq!(
+                stones.len() as u128,
+                count_after(&[125, 17], blinks)?
+            );
+            stones = blink(&stones)?;
+        }
+        Ok(())
+    }
+
+    /// A few stones, mostly with short numbers so
+    /// that every rule comes up.
+    fn stones() -> impl Strategy<Value = String> {
+        vec(
+            prop_oneof![0..100u64, 0..10_000_000u64],
+            1..5,
+        )
+        .prop_map(|stones| {
+            format!("{}\n", stones.iter().join(" "))
+        })
+    }
+
+    #[test]
+    fn test_growth_matches_blink() -> miette::Result<()> {
+        property::check(
+            env!("CARGO_MANIFEST_DIR"),
+            "growth",
+            stones(),
+            |input| {
+                let mut stones =
+                    parse(input).map_err(|e| {
+                        TestCaseError::fail(e.to_string())
+                    })?;
+                let fail = |e: OverflowError| {
+                    TestCaseError::fail(e.to_string())
+                };
+                let growth =
+                    growth(&Rules::puzzle(), &stones, 15)
+                        .map_err(fail)?;
+                for total in growth {
+                    prop_assert_eq!(
+                        stones.len() as u128,
+                        total
+                    );
+                    stones =
+                        blink(&stones).map_err(fail)?;
+                }
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+}
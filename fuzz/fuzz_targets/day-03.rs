@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Both parts must return an error for bad input,
+// never panic.
+fuzz_target!(|input: &str| {
+    let _ = day_03::part1::process(input);
+    let _ = day_03::part2::process(input);
+});
@@ -3,20 +3,20 @@
 [package]
 edition = "2021"
 [dependencies]
-clap = { version = "4.2", features = ["derive"] }
+aoc-client = { path = "../aoc-client" }
+clap = { version = "4.2", features = ["derive", "env"] }
+miette = { version = "7.4", features = ["fancy"] }
 nom = "7.1.3"
-reqwest = { version = "0.11.22", features=["blocking"] }
 ---
 
+use aoc_client::{Client, DEFAULT_MIN_DELAY, DEFAULT_YEAR};
 use clap::{error::ErrorKind, CommandFactory, Parser};
+use miette::{IntoDiagnostic, WrapErr};
 use nom::{
     bytes::complete::tag, character::complete,
     sequence::preceded, IResult,
 };
-use reqwest::{blocking::Client, header::COOKIE};
-use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 #[derive(Parser, Debug)]
 #[clap(version)]
@@ -31,15 +31,31 @@ struct Args {
     /// doing any shenanigans
     #[clap(long)]
     current_working_directory: PathBuf,
+    #[clap(
+        short,
+        long,
+        env = "AOC_YEAR",
+        default_value_t = DEFAULT_YEAR
+    )]
+    year: u32,
+    /// also download the puzzle page as
+    /// `puzzle.html` and its description as
+    /// `puzzle.md`
+    #[clap(long)]
+    puzzle: bool,
+    /// minimum number of seconds between requests
+    #[clap(
+        long,
+        default_value_t = DEFAULT_MIN_DELAY.as_secs()
+    )]
+    min_delay: u64,
 }
 
 fn parse_day(input: &str) -> IResult<&str, u32> {
     preceded(tag("day-"), complete::u32)(input)
 }
 
-fn main() -> Result<(), reqwest::Error> {
-    let session = std::env::var("SESSION")
-        .expect("should have a session token set");
+fn main() -> miette::Result<()> {
     let args = Args::parse();
     let Ok((_, day)) = parse_day(&args.day) else {
         let mut cmd = Args::command();
@@ -53,31 +69,48 @@ fn main() -> Result<(), reqwest::Error> {
         .exit()
     };
 
-    let url = format!(
-        "https://adventofcode.com/2024/day/{day}/input"
-    );
-    println!("sending to `{}`", url);
-
-    let client = Client::new();
-    let input_data = client
-        .get(url)
-        .header(COOKIE, format!("session={session}"))
-        .send()?
-        .text()?;
+    let client = Client::from_env()?
+        .with_year(args.year)
+        .with_cache_dir(
+            args.current_working_directory
+                .join(".aoc-cache"),
+        )
+        .with_min_delay(Duration::from_secs(
+            args.min_delay,
+        ));
+    let day_dir =
+        args.current_working_directory.join(&args.day);
 
+    let input_data = client.input(day)?;
     for filename in ["input1.txt", "input2.txt"] {
-        let file_path = args
-            .current_working_directory
-            .join(&args.day)
-            .join(filename);
-        let mut file = File::create(&file_path)
-            .expect("should be able to create a file");
-
-        file.write_all(input_data.as_bytes()).expect(
-            "should be able to write to input file",
-        );
+        let file_path = day_dir.join(filename);
+        std::fs::write(&file_path, &input_data)
+            .into_diagnostic()
+            .wrap_err_with(|| {
+                format!("writing {}", file_path.display())
+            })?;
         println!("wrote {}", file_path.display());
     }
 
+    if args.puzzle {
+        let html = client.puzzle_html(day)?;
+        let markdown =
+            aoc_client::puzzle::to_markdown(&html)?;
+        for (filename, contents) in
+            [("puzzle.html", html), ("puzzle.md", markdown)]
+        {
+            let file_path = day_dir.join(filename);
+            std::fs::write(&file_path, contents)
+                .into_diagnostic()
+                .wrap_err_with(|| {
+                    format!(
+                        "writing {}",
+                        file_path.display()
+                    )
+                })?;
+            println!("wrote {}", file_path.display());
+        }
+    }
+
     Ok(())
 }